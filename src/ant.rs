@@ -1,6 +1,8 @@
 use std::{
+    collections::{HashMap, VecDeque},
     f32::consts::{PI, TAU},
     marker::PhantomData,
+    mem::discriminant,
     time::Duration,
 };
 
@@ -19,22 +21,42 @@ use rand::prelude::*;
 use bevy_prng::WyRand;
 
 use crate::{
-    colony::{AntCapacity, AntPopulation, Colony, LaborData, LaborPhase},
+    calendar::SimCalendar,
+    colony::{
+        nearest_entrance, AntCapacity, AntPopulation, Colony, EntranceQuery, LaborData, LaborPhase,
+    },
     food::{FoodDeltaEvent, FoodQuant},
-    gametimer::{scaled_time, SimTimer, TickRate},
-    gizmodable::{GizmoDrawOp, GizmoSystemSet, VisualDebug},
+    gametimer::{scaled_time, GameClock, SimTimer, TickRate},
+    gizmodable::{
+        debug_layer_enabled, DebugLayer, DebugLayerAppExt, GizmoDrawOp, GizmoSystemSet, VisualDebug,
+    },
     misc_utility::NaNGuard,
     nav::scent::{ScentMap, ScentSettings, ScentType, WeightType},
     nav::DistanceAwareQuery,
+    stats::ColonyEvent,
+    terrain::{detour_around, push_out_of_obstacles, ObstacleQuery, OBSTACLE_CLEARANCE},
+    transport::{
+        heavy_food_grip_point, GaveUpHauling, HeavyFood, HEAVY_FOOD_GRIP_RADIUS,
+        HEAVY_FOOD_GRIP_TOLERANCE,
+    },
+    weather::Weather,
+    worldgen::WorldBounds,
     AntSpatialMarker, SimState, SoundScape, SpatialMarker,
 };
 
 pub struct AntPlugin;
 
+#[derive(SystemSet, Hash, Debug, PartialEq, Eq, Clone)]
+pub struct NavigationSystems;
+
 impl Plugin for AntPlugin {
     fn build(&self, app: &mut App) {
         app.register_type::<AntSettings>()
             .insert_resource(AntSettings::default())
+            .register_debug_layer(DebugLayer::Nav)
+            .register_debug_layer(DebugLayer::Roles)
+            .register_debug_layer(DebugLayer::ForagerState)
+            .register_debug_layer(DebugLayer::Drift)
             .add_systems(
                 Update,
                 (
@@ -44,16 +66,26 @@ impl Plugin for AntPlugin {
                         nursmaid_ant_behavior,
                         forager_ant_behavior,
                     )
-                        .after(LaborPhase::Task),
+                        .after(LaborPhase::Task)
+                        .run_if(on_timer(Duration::from_secs_f32(ANT_BEHAVIOR_INTERVAL))),
                     (
                         forager_timer_reset,
-                        forager_behavior_debug,
-                        debug_ant_assignment,
-                        nav_debug,
+                        (forager_behavior_debug, annotate_forager_changes)
+                            .run_if(debug_layer_enabled(DebugLayer::ForagerState)),
+                        debug_ant_assignment.run_if(debug_layer_enabled(DebugLayer::Roles)),
+                        nav_debug.run_if(debug_layer_enabled(DebugLayer::Nav)),
+                        drift_debug.run_if(debug_layer_enabled(DebugLayer::Drift)),
                     )
                         .before(GizmoSystemSet::GizmoQueueDraw),
-                    (ant_i_gravity, navigate_move, tokyo)
+                    (
+                        ant_i_gravity,
+                        wind_drift,
+                        steer_inside_bounds,
+                        navigate_move,
+                        tokyo,
+                    )
                         .chain()
+                        .in_set(NavigationSystems)
                         .run_if(in_state(SimState::Playing)),
                 )
                     .chain(),
@@ -61,6 +93,10 @@ impl Plugin for AntPlugin {
             .add_systems(
                 Update,
                 ant_stink.run_if(on_timer(Duration::from_secs_f32(0.25))),
+            )
+            .add_systems(
+                Update,
+                (ant_speed, starve_ants).run_if(in_state(SimState::Playing)),
             );
     }
 }
@@ -69,6 +105,19 @@ impl Plugin for AntPlugin {
 pub struct Ant {
     colony: Entity,
     home: Vec2,
+    // Sim-time at hatching, off the calendar.
+    born: Duration,
+}
+impl Ant {
+    pub fn age(&self, calendar: &SimCalendar) -> Duration {
+        calendar.elapsed().saturating_sub(self.born)
+    }
+    pub fn colony(&self) -> Entity {
+        self.colony
+    }
+    pub fn home(&self) -> Vec2 {
+        self.home
+    }
 }
 #[derive(Component)]
 pub struct JobType;
@@ -106,6 +155,19 @@ pub enum ForagerAnt {
     FollowingTrail,
     BringingHomeFood,
     GoingHomeEmpty,
+    // Gripping something too heavy to carry alone, see transport.rs
+    Hauling(Entity),
+}
+impl ForagerAnt {
+    pub fn describe(&self) -> &'static str {
+        match self {
+            ForagerAnt::Seeking => "seeking food",
+            ForagerAnt::FollowingTrail => "following a trail",
+            ForagerAnt::BringingHomeFood => "bringing food home",
+            ForagerAnt::GoingHomeEmpty => "going home empty",
+            ForagerAnt::Hauling(_) => "hauling",
+        }
+    }
 }
 impl Default for ForagerAnt {
     fn default() -> Self {
@@ -148,13 +210,53 @@ impl Command for Assign<IdleAnt> {
 pub struct Navigate {
     max_speed: f32,
     max_radians_per_sec: f32,
-    move_to: Option<Vec2>,
+    waypoints: VecDeque<Vec2>,
+}
+impl Navigate {
+    pub fn new(max_speed: f32, max_radians_per_sec: f32) -> Self {
+        Navigate {
+            max_speed,
+            max_radians_per_sec,
+            waypoints: VecDeque::with_capacity(NAV_MAX_WAYPOINTS),
+        }
+    }
+    pub fn set_max_speed(&mut self, max_speed: f32) {
+        self.max_speed = max_speed;
+    }
+    pub fn move_to(&self) -> Option<Vec2> {
+        self.waypoints.front().copied()
+    }
+    // Drop whatever we were doing and head straight for `dest`.
+    pub fn set_destination(&mut self, dest: Vec2) {
+        self.waypoints.clear();
+        self.waypoints.push_back(dest);
+    }
+    fn queue(&mut self, dest: Vec2) {
+        if self.waypoints.len() < NAV_MAX_WAYPOINTS {
+            self.waypoints.push_back(dest);
+        }
+    }
+    pub fn clear(&mut self) {
+        self.waypoints.clear();
+    }
+    // We ask for our next waypoint while we're still closing in on the last one, so that we never have to stop and wait for it.
+    fn needs_waypoint(&self, pos: Vec2) -> bool {
+        match self.waypoints.back() {
+            None => true,
+            Some(last) => self.waypoints.len() == 1 && last.distance(pos) <= NAV_LOOKAHEAD,
+        }
+    }
 }
 #[derive(Component)]
-struct Drift {
+pub struct Drift {
     vec: Vec2,
     mag: f32,
 }
+impl Drift {
+    pub fn velocity(&self) -> Vec2 {
+        self.vec * self.mag
+    }
+}
 
 #[derive(Component)]
 pub struct Carried;
@@ -162,13 +264,42 @@ pub struct Carried;
 const ANT_STARTING_MAX_AGE: u64 = 240;
 const ANT_STARTING_CARRY_CAPACITY: i32 = 5;
 const ANT_MOVE_SPEED: f32 = 5.0;
+// The fastest anything can knock an ant off course.
+const ANT_MAX_DRIFT: f32 = 0.9 * ANT_MOVE_SPEED;
 const ANT_SEC_PER_ROTATION: f32 = 5.0;
 const ANT_I_GRAVITY_FACTOR: f32 = 15.0;
 const ANT_I_GRAVITY_MAXIMUM: f32 = 50.0;
+const ANT_OBSTACLE_LOOKAHEAD: f32 = 25.0;
+const ANT_EDGE_PUSH_FACTOR: f32 = 2.0;
+const ANT_BEHAVIOR_INTERVAL: f32 = 0.1;
+pub const ANT_DROP_OFF_RADIUS: f32 = 12.0;
+const FORAGER_ANNOTATION_SECS: f32 = 2.0;
+const FORAGER_ANNOTATION_OFFSET: f32 = 6.0;
+const STARVATION_MIN_SECS: u64 = 60;
+const STARVATION_MAX_SECS: u64 = 180;
+const STARVING_SPEED_FACTOR: f32 = 0.5;
+const NAV_MAX_WAYPOINTS: usize = 4;
+const NAV_LOOKAHEAD: f32 = 6.0;
+const NAV_ARRIVE_RADIUS: f32 = 4.0;
 
 #[derive(Component)]
 pub struct Lifespan;
 
+// Inserted by the colony when it can't feed its ants. Starving ants slow down, and if nobody brings food home in time, die.
+#[derive(Component)]
+pub struct Starving {
+    time: Timer,
+}
+impl Starving {
+    // Everyone holds out for a slightly different amount of time, so a famine thins the colony out rather than wiping it out in one frame.
+    pub fn random(rng: &mut impl Rng) -> Self {
+        let grace = rng.gen_range(STARVATION_MIN_SECS..STARVATION_MAX_SECS);
+        Starving {
+            time: Timer::new(Duration::from_secs(grace), TimerMode::Once),
+        }
+    }
+}
+
 //TODO - probably make this keyed to colony entity at some point
 #[derive(Resource, Reflect)]
 #[reflect(Resource)]
@@ -210,8 +341,9 @@ impl Command for AntSpawn {
             EventWriter<SoundScape>,
             Res<AssetServer>,
             Query<(&AntCapacity, &mut AntPopulation), With<Colony>>,
+            Res<SimCalendar>,
         )> = SystemState::from_world(world);
-        let (mut commands, _ant_settings, mut soundscape, assets, mut q_colony) =
+        let (mut commands, _ant_settings, mut soundscape, assets, mut q_colony, calendar) =
             state.get_mut(world);
         let (ant_cap, mut ant_pop) = q_colony.single_mut();
         if ant_pop.0 < ant_cap.0 {
@@ -235,12 +367,9 @@ impl Command for AntSpawn {
                         ant: Ant {
                             colony: self.colony_entity,
                             home: self.home,
+                            born: calendar.elapsed(),
                         },
-                        nav: Navigate {
-                            max_speed: ANT_MOVE_SPEED,
-                            max_radians_per_sec: TAU / ANT_SEC_PER_ROTATION,
-                            move_to: None,
-                        },
+                        nav: Navigate::new(ANT_MOVE_SPEED, TAU / ANT_SEC_PER_ROTATION),
                         drift: Drift {
                             vec: Vec2::ZERO,
                             mag: 0.0,
@@ -276,6 +405,7 @@ impl<'a, 'b> AntCommandsExt for Commands<'a, 'b> {
 
 fn navigate_move(
     mut q: Query<(&GlobalTransform, &mut Transform, &mut Navigate)>,
+    obstacles: ObstacleQuery,
     game_time: Res<Time>,
     game_clock: Res<State<TickRate>>,
 ) {
@@ -285,27 +415,62 @@ fn navigate_move(
 
     q.par_iter_mut()
         .for_each(|(global_transform, mut transform, mut nav)| {
-            if let Some(destination) = nav.move_to {
+            let mut pos_2d = global_transform.translation().xy();
+            // Pass-through waypoints only need to be got near - we'll already be bending toward the next one.
+            while nav.waypoints.len() > 1
+                && nav
+                    .move_to()
+                    .is_some_and(|waypoint| waypoint.distance(pos_2d) <= NAV_LOOKAHEAD * 0.5)
+            {
+                nav.waypoints.pop_front();
+            }
+            if let Some(mut destination) = nav.move_to() {
                 if destination.is_nan() {
-                    nav.move_to = None;
+                    nav.waypoints.pop_front();
                     return;
                 }
-                let mut pos_2d = global_transform.translation().xy();
-                let max_speed = destination.distance(pos_2d);
-                let mut scaled_speed = (nav.max_speed * frame_delta).clamp(0.0, max_speed);
-                let scaled_rot_speed = nav.max_radians_per_sec * frame_delta;
+                // Nobody is getting to the middle of a rock, settle for the edge of it.
+                let reachable = push_out_of_obstacles(&obstacles, destination, OBSTACLE_CLEARANCE);
+                if reachable != destination {
+                    destination = reachable;
+                    nav.waypoints[0] = destination;
+                }
+                let is_final = nav.waypoints.len() == 1;
+                let distance = destination.distance(pos_2d);
 
-                let mut vec = (destination - pos_2d).normalize();
-                let facing = (transform.rotation * Vec3::Y).xy();
-                let angle_delta = vec.angle_between(facing);
+                // Seek at full speed toward pass-through waypoints, arrive gently at the last one.
+                let arrive_factor = if is_final {
+                    (distance / NAV_ARRIVE_RADIUS).clamp(0.25, 1.0)
+                } else {
+                    1.0
+                };
+                let mut scaled_speed =
+                    (nav.max_speed * arrive_factor * frame_delta).clamp(0.0, distance);
+                let scaled_rot_speed = nav.max_radians_per_sec * frame_delta;
 
                 //If we're ~ one frame away just teleport there - this fixes a host of xeno's paradox type edge-cases.
-                if destination.distance(pos_2d) <= (scaled_speed * 1.3) {
+                if is_final && distance <= (scaled_speed * 1.3) {
                     transform.translation = destination.extend(2.0);
-                    nav.move_to = None;
+                    nav.clear();
                     return;
                 }
 
+                // Lookahead - as we close in on this waypoint start aiming for the next, which rounds off the corners.
+                let mut aim = destination;
+                if let Some(next) = nav.waypoints.get(1) {
+                    if distance < NAV_LOOKAHEAD {
+                        aim = destination.lerp(*next, 1.0 - distance / NAV_LOOKAHEAD);
+                    }
+                }
+
+                // If there's something in the way, steer for a point beside it rather than straight at our destination.
+                let steer_to =
+                    detour_around(&obstacles, pos_2d, aim, ANT_OBSTACLE_LOOKAHEAD).unwrap_or(aim);
+
+                let mut vec = (steer_to - pos_2d).normalize();
+                let facing = (transform.rotation * Vec3::Y).xy();
+                let angle_delta = vec.angle_between(facing);
+
                 // Figure out if our destination is inside our turn radius
                 let turn_radius = nav.max_speed / nav.max_radians_per_sec;
                 let face_angle = Vec2::Y.angle_between(facing);
@@ -315,12 +480,12 @@ fn navigate_move(
 
                 //If our destination is within our deadzones, scale down our speed based on the arc we'd need to make to get there
 
-                if destination.distance(left_void_center) < turn_radius
-                    || destination.distance(right_void_center) < turn_radius
+                if steer_to.distance(left_void_center) < turn_radius
+                    || steer_to.distance(right_void_center) < turn_radius
                 {
-                    scaled_speed = nav.max_radians_per_sec * (destination.distance(pos_2d) / 2.0)
+                    scaled_speed = nav.max_radians_per_sec * (steer_to.distance(pos_2d) / 2.0)
                         / angle_delta.cos();
-                    scaled_speed = (scaled_speed * frame_delta).clamp(0.0, max_speed);
+                    scaled_speed = (scaled_speed * frame_delta).clamp(0.0, distance);
                 }
 
                 if f32::abs(angle_delta) > scaled_rot_speed {
@@ -333,6 +498,7 @@ fn navigate_move(
                 vec *= scaled_speed;
 
                 pos_2d += vec;
+                pos_2d = push_out_of_obstacles(&obstacles, pos_2d, 0.0);
 
                 transform.translation = Vec3::from((pos_2d, 2.));
             }
@@ -340,16 +506,39 @@ fn navigate_move(
 }
 fn nav_debug(mut q: Query<(&Transform, &Navigate, &mut VisualDebug)>) {
     q.iter_mut().for_each(|(transform, nav, mut dbg)| {
-        if let Some(destination) = nav.move_to {
-            dbg.add(GizmoDrawOp::line(
-                transform.translation.truncate(),
-                destination,
-                Color::GREEN,
-            ));
+        if nav.waypoints.is_empty() {
+            return;
         }
+        let points = std::iter::once(transform.translation.truncate())
+            .chain(nav.waypoints.iter().copied())
+            .collect();
+        dbg.add(GizmoDrawOp::polyline(points, Color::GREEN));
     });
 }
 
+fn steer_inside_bounds(
+    mut q: Query<(&Transform, &mut Navigate, &mut Drift), With<Ant>>,
+    bounds: Res<WorldBounds>,
+    time: Res<Time>,
+) {
+    let interior = bounds.interior();
+    q.par_iter_mut()
+        .for_each(|(transform, mut nav, mut drift)| {
+            for waypoint in nav.waypoints.iter_mut() {
+                *waypoint = waypoint.clamp(interior.min, interior.max);
+            }
+            let pos = transform.translation.xy();
+            let overshoot = pos - pos.clamp(interior.min, interior.max);
+            if overshoot != Vec2::ZERO {
+                // The further past the edge we wander, the harder we get pushed back.
+                let push = -overshoot * ANT_EDGE_PUSH_FACTOR * time.delta_seconds();
+                let sum_vec = drift.vec * drift.mag + push;
+                drift.vec = sum_vec.normalize_or_zero();
+                drift.mag = sum_vec.length().nan_guard(0.0);
+            }
+        });
+}
+
 fn ant_i_gravity(
     ant_locations: DistanceAwareQuery<AntSpatialMarker, &GlobalTransform, With<Ant>>,
     ant_settings: Res<AntSettings>,
@@ -398,27 +587,28 @@ fn ant_i_gravity(
             .min(ant_settings.ant_i_gravity_max);
     })
 }
-fn tokyo(
-    mut q: Query<(&mut Transform, &mut VisualDebug, &mut Navigate, &mut Drift), With<Ant>>,
-    time: Res<Time>,
-) {
-    let max_drift = 0.9 * ANT_MOVE_SPEED;
+fn drift_debug(mut q: Query<(&Transform, &Drift, &mut VisualDebug), With<Ant>>) {
+    q.iter_mut().for_each(|(transform, drift, mut dbg)| {
+        if drift.mag > 0.1 {
+            dbg.add(GizmoDrawOp::arrow(
+                transform.translation.xy(),
+                transform.translation.xy() + (drift.vec * drift.mag),
+                Color::PURPLE,
+            ));
+        }
+    });
+}
+fn tokyo(mut q: Query<(&mut Transform, &mut Navigate, &mut Drift), With<Ant>>, time: Res<Time>) {
     q.par_iter_mut()
-        .for_each(|(mut transform, mut dbg, mut nav, mut drift)| {
+        .for_each(|(mut transform, mut nav, mut drift)| {
             if drift.mag > 0.1 {
                 let scaled_magnitude =
-                    (drift.mag.clamp(0.0, max_drift) * time.delta_seconds()).nan_guard(0.0);
+                    (drift.mag.clamp(0.0, ANT_MAX_DRIFT) * time.delta_seconds()).nan_guard(0.0);
                 let adj = (scaled_magnitude * drift.vec).nan_guard(Vec2::ZERO);
-                let zed = transform.translation.z;
-                dbg.add(GizmoDrawOp::line(
-                    transform.translation.xy(),
-                    transform.translation.xy() + (drift.vec * drift.mag),
-                    Color::PURPLE,
-                ));
-                if let Some(dest) = nav.move_to {
-                    nav.move_to = Some(dest + adj);
+                for waypoint in nav.waypoints.iter_mut() {
+                    *waypoint += adj;
                 }
-                transform.translation += adj.extend(zed);
+                transform.translation += adj.extend(0.0);
 
                 drift.mag -= scaled_magnitude * 1.1;
             }
@@ -516,7 +706,7 @@ fn task_ants(
         if nurse_vaccancies > 0
             && !matches!(
                 behavior,
-                ForagerAnt::BringingHomeFood | ForagerAnt::FollowingTrail
+                ForagerAnt::BringingHomeFood | ForagerAnt::FollowingTrail | ForagerAnt::Hauling(_)
             )
         {
             commands.apply_assignment::<NursemaidAnt>(entity);
@@ -526,7 +716,7 @@ fn task_ants(
         if forager_vaccancies < 0
             && !matches!(
                 behavior,
-                ForagerAnt::BringingHomeFood | ForagerAnt::FollowingTrail
+                ForagerAnt::BringingHomeFood | ForagerAnt::FollowingTrail | ForagerAnt::Hauling(_)
             )
         {
             commands.apply_assignment::<IdleAnt>(entity);
@@ -547,17 +737,18 @@ fn idle_ant_behavior(
         (With<IdleAnt>, Without<NursemaidAnt>, Without<ForagerAnt>),
     >,
     mut scentmap: ResMut<ScentMap>,
+    scent_settings: Res<ScentSettings>,
     mut rng: ResMut<GlobalEntropy<WyRand>>,
 ) {
     q.iter_mut()
         .for_each(|(ant, transform, local_transform, mut nav)| {
-            if let Some(_) = nav.move_to {
+            if !nav.needs_waypoint(transform.translation().xy()) {
                 return;
             }
             let distance_home = transform.translation().xy().distance(ant.home);
             if distance_home >= 30.0 {
-                if let Some(homebound_pos) = scentmap.strongest_smell_weighted(
-                    10.0,
+                if let Some(homebound_pos) = scentmap.strongest_smell(
+                    &scent_settings,
                     ScentType::AntSmell,
                     WeightType::CloserTo(ant.home),
                     transform,
@@ -574,16 +765,16 @@ fn idle_ant_behavior(
                         let dest = self_pos + dest_vec;
 
                         //commands.entity(entity).insert(MoveTo(dest));
-                        nav.move_to = Some(dest);
+                        nav.queue(dest);
                     }
                 } else {
                     let dest = select_random_pos_along_bearing(local_transform, ant.home, &mut rng);
-                    nav.move_to = Some(dest);
+                    nav.queue(dest);
                 }
                 return;
             }
             let new_pos = select_random_wander_pos(local_transform, &mut rng);
-            nav.move_to = Some(new_pos);
+            nav.queue(new_pos);
         })
 }
 fn debug_ant_assignment(
@@ -630,18 +821,19 @@ fn nursmaid_ant_behavior(
         (With<NursemaidAnt>, Without<IdleAnt>, Without<ForagerAnt>),
     >,
     mut scentmap: ResMut<ScentMap>,
+    scent_settings: Res<ScentSettings>,
     mut rng: ResMut<GlobalEntropy<WyRand>>,
 ) {
     //TODO - make this "real"
     q.iter_mut()
         .for_each(|(ant, transform, local_transform, mut nav)| {
-            if let Some(_) = nav.move_to {
+            if !nav.needs_waypoint(transform.translation().xy()) {
                 return;
             }
             let distance_home = transform.translation().xy().distance(ant.home);
             if distance_home >= 45.0 {
-                if let Some(homebound_pos) = scentmap.strongest_smell_weighted(
-                    10.0,
+                if let Some(homebound_pos) = scentmap.strongest_smell(
+                    &scent_settings,
                     ScentType::AntSmell,
                     WeightType::CloserTo(ant.home),
                     transform,
@@ -657,16 +849,16 @@ fn nursmaid_ant_behavior(
                         dest_vec *= self_pos.distance(scent_dest) + 5.0;
                         let dest = self_pos + dest_vec;
 
-                        nav.move_to = Some(dest);
+                        nav.queue(dest);
                     }
                 } else {
                     let dest = select_random_pos_along_bearing(local_transform, ant.home, &mut rng);
-                    nav.move_to = Some(dest);
+                    nav.queue(dest);
                 }
                 return;
             }
             let new_pos = select_random_wander_pos(local_transform, &mut rng);
-            nav.move_to = Some(new_pos);
+            nav.queue(new_pos);
         })
 }
 
@@ -680,40 +872,68 @@ fn forager_ant_behavior(
             &mut Navigate,
             &mut SimTimer,
             &Children,
+            Option<&mut GaveUpHauling>,
         ),
         (Without<IdleAnt>, Without<NursemaidAnt>),
     >,
     mut scentmap: ResMut<ScentMap>,
+    scent_settings: Res<ScentSettings>,
     mut rng: ResMut<GlobalEntropy<WyRand>>,
     ant_settings: Res<AntSettings>,
     mut foodevents: EventWriter<FoodDeltaEvent>,
     carried_q: Query<(Entity, &FoodQuant), (With<Parent>, With<Carried>)>,
     space: DistanceAwareQuery<
         SpatialMarker,
-        (Entity, &GlobalTransform, &FoodQuant),
+        (Entity, &GlobalTransform, &FoodQuant, Option<&HeavyFood>),
         (Without<Carried>, Without<Colony>),
     >,
+    heavy_q: Query<(&GlobalTransform, &HeavyFood)>,
+    entrances: EntranceQuery,
 ) {
     q.iter_mut().for_each(
-        |(ant, mut behavior, transform, local_transform, mut nav, seek_timer, children)| {
+        |(
+            ant,
+            mut behavior,
+            transform,
+            local_transform,
+            mut nav,
+            mut seek_timer,
+            children,
+            gave_up,
+        )| {
             let mypos = transform.translation().xy();
-            if let Some(_) = nav.move_to {
+            if !nav.needs_waypoint(transform.translation().xy()) {
                 return;
             }
 
-            let food_in_sight: Vec<(Entity, &GlobalTransform, &FoodQuant)> =
-                space.within_distance(mypos, 60.0).collect();
+            // Heavy food with a full crew, or that we've already given up on, might as well not be there.
+            let given_up_on = gave_up.as_ref().and_then(|gave_up| gave_up.0);
+            let food_in_sight: Vec<(Entity, &GlobalTransform, &FoodQuant, Option<&HeavyFood>)> =
+                space
+                    .within_distance(mypos, 60.0)
+                    .filter(|(food_ent, _, _, heavy)| {
+                        !heavy.is_some_and(|heavy| heavy.is_full())
+                            && given_up_on != Some(*food_ent)
+                    })
+                    .collect();
             let food_nearby = food_in_sight.len() > 0;
 
             let mut move_to_nearest_chunk = || {
                 let mut pos_of_nearest_chunk: Vec2 = mypos + Vec2::from((120.0, 120.0));
                 let mut res_behavior: Option<ForagerAnt> = None;
 
-                for (food_ent, food_xform, food_q) in food_in_sight.iter() {
+                for (food_ent, food_xform, food_q, heavy) in food_in_sight.iter() {
                     let foodpos = food_xform.translation().xy();
                     let taxi = (foodpos - mypos).abs().to_array().into_iter().sum();
                     let this_dist = mypos.distance(foodpos).nan_guard(taxi);
-                    if this_dist <= food_q.interaction_distance() {
+                    // Too big to nibble - grab hold of it and wait for the rest of the crew.
+                    if heavy.is_some()
+                        && this_dist <= HEAVY_FOOD_GRIP_RADIUS + HEAVY_FOOD_GRIP_TOLERANCE
+                    {
+                        nav.clear();
+                        return Some(ForagerAnt::Hauling(*food_ent));
+                    }
+                    if heavy.is_none() && this_dist <= food_q.interaction_distance() {
                         for child in children.iter() {
                             if let Ok(_) = carried_q.get(*child) {
                                 foodevents.send(FoodDeltaEvent {
@@ -723,7 +943,7 @@ fn forager_ant_behavior(
                                 });
 
                                 res_behavior = Some(ForagerAnt::BringingHomeFood);
-                                nav.move_to = None;
+                                nav.clear();
                                 return res_behavior;
                             }
                         }
@@ -736,7 +956,7 @@ fn forager_ant_behavior(
                         let nearest = mypos.distance(pos_of_nearest_chunk).nan_guard(ntaxi);
                         if this_dist <= nearest {
                             pos_of_nearest_chunk = foodpos;
-                            nav.move_to = Some(pos_of_nearest_chunk);
+                            nav.set_destination(pos_of_nearest_chunk);
                             res_behavior = Some(ForagerAnt::FollowingTrail);
                         }
                     }
@@ -745,12 +965,22 @@ fn forager_ant_behavior(
             };
 
             match (*behavior, food_nearby) {
+                (ForagerAnt::Hauling(item), _) => {
+                    let Ok((item_transform, heavy)) = heavy_q.get(item) else {
+                        *behavior = ForagerAnt::default();
+                        return;
+                    };
+                    if heavy.is_gripping(item_transform.translation().xy(), mypos) {
+                        return;
+                    }
+                    let grip = heavy_food_grip_point(item_transform.translation().xy(), mypos);
+                    nav.set_destination(grip);
+                }
                 (ForagerAnt::BringingHomeFood, _) | (ForagerAnt::GoingHomeEmpty, false) => {
-                    let distance_to_home = mypos.distance(ant.home);
-                    let distance_to_origin = mypos.distance(Vec2::ZERO);
-                    if distance_to_home <= 3.0
-                        || (distance_to_home > distance_to_origin && distance_to_origin <= 25.0)
-                    {
+                    // Head for whichever of our colony's entrances is closest.
+                    let home = nearest_entrance(&entrances, ant.colony, mypos).unwrap_or(ant.home);
+                    let distance_to_home = mypos.distance(home);
+                    if distance_to_home <= ANT_DROP_OFF_RADIUS {
                         for child in children.iter() {
                             if let Ok((entity, carried_food)) = carried_q.get(*child) {
                                 foodevents.send(FoodDeltaEvent {
@@ -762,21 +992,24 @@ fn forager_ant_behavior(
                         }
 
                         *behavior = ForagerAnt::default();
+                        if let Some(mut gave_up) = gave_up {
+                            gave_up.0 = None;
+                        }
 
                         return;
                     }
                     if distance_to_home <= 60.0 {
-                        nav.move_to = Some(ant.home);
+                        nav.set_destination(home);
 
                         return;
                     }
-                    if let Some(homebound_pos) = scentmap.strongest_smell_weighted(
-                        10.0,
+                    if let Some(homebound_pos) = scentmap.strongest_smell(
+                        &scent_settings,
                         ScentType::AntSmell,
-                        WeightType::CloserTo(ant.home),
+                        WeightType::CloserTo(home),
                         transform,
                     ) {
-                        let mut scent_vec = (ant.home - homebound_pos).normalize_or_zero();
+                        let mut scent_vec = (home - homebound_pos).normalize_or_zero();
                         scent_vec *= 10.0;
 
                         let scent_dest = homebound_pos + scent_vec;
@@ -784,29 +1017,32 @@ fn forager_ant_behavior(
                         let mut dest_vec = (scent_dest - mypos).normalize_or_zero();
                         dest_vec *= mypos.distance(scent_dest) + 5.0;
                         let dest = mypos + dest_vec;
-                        if dest.distance(ant.home) < distance_to_home {
-                            nav.move_to = Some(dest);
+                        if dest.distance(home) < distance_to_home {
+                            nav.queue(dest);
                             return;
                         }
                     }
                     // pretend we're facing home so that we get a random destination in a vaguely homeward direction.
 
-                    let dest =
-                        select_random_pos_along_bearing(&local_transform, ant.home, &mut rng);
+                    let dest = select_random_pos_along_bearing(&local_transform, home, &mut rng);
 
-                    nav.move_to = Some(dest);
+                    nav.queue(dest);
                 }
                 (
                     ForagerAnt::Seeking | ForagerAnt::FollowingTrail | ForagerAnt::GoingHomeEmpty,
                     true,
                 ) => {
                     if let Some(new_behavior) = move_to_nearest_chunk() {
+                        // The crew gets as long to turn up as we'd spend seeking, see haul_heavy_food.
+                        if let ForagerAnt::Hauling(_) = new_behavior {
+                            seek_timer.time.reset();
+                        }
                         *behavior = new_behavior;
                     }
                 }
                 (ForagerAnt::Seeking | ForagerAnt::FollowingTrail, false) => {
-                    if let Some(outbound_pos) = scentmap.strongest_smell_weighted(
-                        10.0,
+                    if let Some(outbound_pos) = scentmap.strongest_smell(
+                        &scent_settings,
                         ScentType::FoundFoodSmell,
                         WeightType::FurtherFrom(ant.home),
                         transform,
@@ -819,7 +1055,7 @@ fn forager_ant_behavior(
                         let mut dest_vec = (scent_dest - mypos).normalize_or_zero();
                         dest_vec *= mypos.distance(scent_dest) + 5.0;
                         let dest = mypos + dest_vec;
-                        nav.move_to = Some(dest);
+                        nav.queue(dest);
 
                         *behavior = ForagerAnt::FollowingTrail;
 
@@ -833,8 +1069,8 @@ fn forager_ant_behavior(
                         return;
                     }
 
-                    if let Some(pos) = scentmap.strongest_smell_weighted(
-                        10.0,
+                    if let Some(pos) = scentmap.strongest_smell(
+                        &scent_settings,
                         ScentType::AntSmell,
                         WeightType::Unweighted,
                         transform,
@@ -844,22 +1080,27 @@ fn forager_ant_behavior(
                         new_vec *= -30.0;
 
                         let dest = new_vec + transform.translation().xy();
-                        nav.move_to = Some(dest);
+                        nav.queue(dest);
 
                         return;
                     }
                     let dest =
                         select_random_pos_along_bearing(&local_transform, ant.home, &mut rng);
-                    nav.move_to = Some(dest);
+                    nav.queue(dest);
                 }
             }
         },
     )
 }
-fn forager_timer_reset(mut q: Query<(&ForagerAnt, &mut SimTimer)>) {
+fn forager_timer_reset(mut q: Query<(&ForagerAnt, &mut SimTimer)>, heavy_q: Query<&HeavyFood>) {
     q.iter_mut()
         .for_each(|(behavior, mut seek_timer)| match *behavior {
             ForagerAnt::Seeking => {}
+            // Still waiting on the rest of the crew.
+            ForagerAnt::Hauling(item)
+                if heavy_q
+                    .get(item)
+                    .is_ok_and(|heavy| heavy.carriers < heavy.carriers_needed) => {}
             _ => {
                 seek_timer.time.reset();
             }
@@ -885,10 +1126,40 @@ fn forager_behavior_debug(
                 ForagerAnt::BringingHomeFood => {
                     dbg.add(GizmoDrawOp::circle(mypos, 5.0, Color::YELLOW))
                 }
+                ForagerAnt::Hauling(_) => dbg.add(GizmoDrawOp::circle(mypos, 5.0, Color::ORANGE)),
             };
         });
 }
 
+// Labels forager decisions where they're made - e.g. an ant giving up and heading home empty.
+fn annotate_forager_changes(
+    mut last_seen: Local<HashMap<Entity, ForagerAnt>>,
+    mut removed: RemovedComponents<ForagerAnt>,
+    mut q: Query<(Entity, &ForagerAnt, &GlobalTransform, &mut VisualDebug), Changed<ForagerAnt>>,
+) {
+    for entity in removed.read() {
+        last_seen.remove(&entity);
+    }
+    q.iter_mut()
+        .for_each(|(entity, behavior, transform, mut dbg)| {
+            // Behaviors get re-assigned the same state all the time, so only a different state counts as a decision.
+            if last_seen
+                .insert(entity, *behavior)
+                .is_some_and(|previous| discriminant(&previous) == discriminant(behavior))
+            {
+                return;
+            }
+            dbg.add_timed(
+                GizmoDrawOp::text(
+                    transform.translation().xy() + Vec2::Y * FORAGER_ANNOTATION_OFFSET,
+                    format!("now {}", behavior.describe()),
+                    Color::WHITE,
+                ),
+                FORAGER_ANNOTATION_SECS,
+            );
+        });
+}
+
 fn ant_stink(
     mut scentmap: ResMut<ScentMap>,
     settings: Res<ScentSettings>,
@@ -904,10 +1175,70 @@ fn ant_stink(
         scentmap.log_scent(max_smell, transform, ScentType::AntSmell, strength);
 
         match behavior {
-            Some(ForagerAnt::BringingHomeFood) => {
+            Some(ForagerAnt::BringingHomeFood | ForagerAnt::Hauling(_)) => {
                 scentmap.log_scent(max_smell, transform, ScentType::FoundFoodSmell, strength);
             }
             _ => {}
         }
     })
 }
+
+// Hunger and the weather both slow ants down - only bother recalculating for the ants where one of those has changed.
+fn ant_speed(
+    weather: Res<Weather>,
+    born: Query<Entity, Added<Ant>>,
+    starting: Query<Entity, Added<Starving>>,
+    mut fed: RemovedComponents<Starving>,
+    mut q: Query<(&mut Navigate, Has<Starving>), With<Ant>>,
+) {
+    let speed_for = |starving: bool| {
+        let hunger = if starving { STARVING_SPEED_FACTOR } else { 1.0 };
+        ANT_MOVE_SPEED * hunger * weather.ant_speed_factor()
+    };
+    if weather.is_changed() {
+        q.iter_mut()
+            .for_each(|(mut nav, starving)| nav.set_max_speed(speed_for(starving)));
+        return;
+    }
+    for entity in born.iter().chain(starting.iter()).chain(fed.read()) {
+        if let Ok((mut nav, starving)) = q.get_mut(entity) {
+            nav.set_max_speed(speed_for(starving));
+        }
+    }
+}
+
+// Uses the same clock as tokyo, and never pushes harder than tokyo will carry an ant, so the drift dies down soon after the wind does.
+fn wind_drift(weather: Res<Weather>, time: Res<Time>, mut q: Query<&mut Drift, With<Ant>>) {
+    let wind = weather.wind();
+    if wind == Vec2::ZERO {
+        return;
+    }
+    let gust = wind * time.delta_seconds();
+    q.par_iter_mut().for_each(|mut drift| {
+        let sum_vec = drift.vec * drift.mag + gust;
+        drift.vec = sum_vec.normalize_or_zero();
+        drift.mag = sum_vec.length().min(ANT_MAX_DRIFT).nan_guard(0.0);
+    });
+}
+
+fn starve_ants(
+    mut commands: Commands,
+    clock: Res<GameClock>,
+    mut sounds: EventWriter<SoundScape>,
+    mut colony_events: EventWriter<ColonyEvent>,
+    mut q: Query<(Entity, &Ant, &mut Starving)>,
+    mut col_q: Query<&mut AntPopulation, With<Colony>>,
+) {
+    for (entity, ant, mut starving) in q.iter_mut() {
+        starving.time.tick(clock.delta);
+        if !starving.time.finished() {
+            continue;
+        }
+        if let Ok(mut ant_pop) = col_q.get_mut(ant.colony) {
+            ant_pop.0 -= 1;
+        }
+        sounds.send(SoundScape::AntDeath);
+        colony_events.send(ColonyEvent::AntDied);
+        commands.entity(entity).despawn_recursive();
+    }
+}
@@ -5,7 +5,7 @@ use bevy_persistent::prelude::*;
 use serde::{Deserialize, Serialize};
 use std::path::Path;
 
-use crate::InitializationPhase;
+use crate::{playerinput::ControlBindings, InitializationPhase};
 
 pub struct AppSettingsPlugin;
 impl Plugin for AppSettingsPlugin {
@@ -44,6 +44,33 @@ enum UserSettings {
 
         display_first_time_help: bool,
     },
+    // Adds the player's control bindings.
+    V2 {
+        global_volume: f32,
+        sfx_volume: f32,
+        music_volume: f32,
+
+        resolution: (f32, f32),
+        fullscreen: bool,
+
+        display_first_time_help: bool,
+
+        controls: ControlBindings,
+    },
+    // Adds the camera's optional pan and zoom modes.
+    V3 {
+        global_volume: f32,
+        sfx_volume: f32,
+        music_volume: f32,
+
+        resolution: (f32, f32),
+        fullscreen: bool,
+
+        display_first_time_help: bool,
+
+        controls: ControlBindings,
+        camera: CameraSettings,
+    },
 }
 impl UserSettings {
     fn volume_settings(&self) -> VolumeSettings {
@@ -53,6 +80,18 @@ impl UserSettings {
                 sfx_volume,
                 music_volume,
                 ..
+            }
+            | Self::V2 {
+                global_volume,
+                sfx_volume,
+                music_volume,
+                ..
+            }
+            | Self::V3 {
+                global_volume,
+                sfx_volume,
+                music_volume,
+                ..
             } => {
                 let mut settings = VolumeSettings {
                     global_user_setting: *global_volume,
@@ -71,16 +110,96 @@ impl UserSettings {
                 resolution,
                 fullscreen,
                 ..
+            }
+            | UserSettings::V2 {
+                resolution,
+                fullscreen,
+                ..
+            }
+            | UserSettings::V3 {
+                resolution,
+                fullscreen,
+                ..
             } => DisplaySettings {
                 resolution: *resolution,
                 fullscreen: *fullscreen,
             },
         }
     }
-    //placeholder for future versions.
-    fn migrate(&mut self) {
+    fn control_bindings(&self) -> ControlBindings {
         match self {
-            Self::V1 { .. } => {}
+            UserSettings::V1 { .. } => ControlBindings::default(),
+            UserSettings::V2 { controls, .. } | UserSettings::V3 { controls, .. } => {
+                controls.clone()
+            }
+        }
+    }
+    fn camera_settings(&self) -> CameraSettings {
+        match self {
+            UserSettings::V1 { .. } | UserSettings::V2 { .. } => CameraSettings::default(),
+            UserSettings::V3 { camera, .. } => camera.clone(),
+        }
+    }
+    fn display_first_time_help(&self) -> bool {
+        match self {
+            UserSettings::V1 {
+                display_first_time_help,
+                ..
+            }
+            | UserSettings::V2 {
+                display_first_time_help,
+                ..
+            }
+            | UserSettings::V3 {
+                display_first_time_help,
+                ..
+            } => *display_first_time_help,
+        }
+    }
+    // Brings settings saved by older versions up to date, one version at a time.
+    // V1 predates rebindable controls and V2 the camera modes, so each gets the defaults for what it's missing.
+    fn migrate(&mut self) {
+        if let Self::V1 {
+            global_volume,
+            sfx_volume,
+            music_volume,
+            resolution,
+            fullscreen,
+            display_first_time_help,
+        } = self
+        {
+            info!("migrating user settings from V1 to V2");
+            *self = Self::V2 {
+                global_volume: *global_volume,
+                sfx_volume: *sfx_volume,
+                music_volume: *music_volume,
+                resolution: *resolution,
+                fullscreen: *fullscreen,
+                display_first_time_help: *display_first_time_help,
+                controls: ControlBindings::default(),
+            };
+        }
+        if let Self::V2 {
+            global_volume,
+            sfx_volume,
+            music_volume,
+            resolution,
+            fullscreen,
+            display_first_time_help,
+            controls,
+        } = self
+        {
+            info!("migrating user settings from V2 to V3");
+            *self = Self::V3 {
+                global_volume: *global_volume,
+                sfx_volume: *sfx_volume,
+                music_volume: *music_volume,
+                resolution: *resolution,
+                fullscreen: *fullscreen,
+                display_first_time_help: *display_first_time_help,
+                controls: controls.clone(),
+                camera: CameraSettings::default(),
+            };
         }
     }
 }
@@ -88,13 +207,15 @@ impl UserSettings {
 #[cfg(target_arch = "wasm32")]
 impl Default for UserSettings {
     fn default() -> Self {
-        Self::V1 {
+        Self::V3 {
             global_volume: 0.5,
             sfx_volume: 0.5,
             music_volume: 0.5,
             resolution: (1280., 720.),
             fullscreen: false,
             display_first_time_help: true,
+            controls: ControlBindings::default(),
+            camera: CameraSettings::default(),
         }
     }
 }
@@ -102,13 +223,15 @@ impl Default for UserSettings {
 #[cfg(not(target_arch = "wasm32"))]
 impl Default for UserSettings {
     fn default() -> Self {
-        Self::V1 {
+        Self::V3 {
             global_volume: 0.5,
             sfx_volume: 0.5,
             music_volume: 0.5,
             resolution: (1280., 720.),
             fullscreen: true,
             display_first_time_help: true,
+            controls: ControlBindings::default(),
+            camera: CameraSettings::default(),
         }
     }
 }
@@ -134,6 +257,28 @@ pub struct DisplaySettings {
     pub resolution: (f32, f32),
     pub fullscreen: bool,
 }
+// The optional ways of moving the camera around, on top of the keys and middle-mouse drag.
+#[derive(Resource, Reflect, Clone, Serialize, Deserialize)]
+#[reflect(Resource)]
+pub struct CameraSettings {
+    pub edge_scroll: bool,
+    // Left-drag on empty ground.
+    pub drag_pan: bool,
+    pub zoom_to_cursor: bool,
+    // The camera keeps gliding for a moment after letting go.
+    pub inertia: bool,
+}
+impl Default for CameraSettings {
+    fn default() -> Self {
+        Self {
+            edge_scroll: false,
+            drag_pan: true,
+            zoom_to_cursor: true,
+            inertia: true,
+        }
+    }
+}
+
 #[cfg(not(target_arch = "wasm32"))]
 impl Default for DisplaySettings {
     fn default() -> Self {
@@ -194,49 +339,28 @@ pub struct ApplicationSettings<'w> {
     user_settings: ResMut<'w, Persistent<UserSettings>>,
     volume_settings: Res<'w, VolumeSettings>,
     display_settings: Res<'w, DisplaySettings>,
+    control_bindings: Res<'w, ControlBindings>,
+    camera_settings: Res<'w, CameraSettings>,
 }
 impl ApplicationSettings<'_> {
     pub fn save_app_settings(&mut self) {
-        //AFAICT this is a false positive on this lint error
-        #[allow(unused_mut)]
-        let mut da_settings = self.user_settings.get_mut();
-        let UserSettings::V1 {
-            display_first_time_help,
-            ..
-        } = da_settings;
-        *da_settings = UserSettings::V1 {
+        let display_first_time_help = self.user_settings.display_first_time_help();
+        self.store(display_first_time_help);
+    }
+    #[allow(dead_code)]
+    pub fn register_introductory_help(&mut self) {
+        self.store(false);
+    }
+    fn store(&mut self, display_first_time_help: bool) {
+        *self.user_settings.get_mut() = UserSettings::V3 {
             global_volume: self.volume_settings.global_user_setting,
             sfx_volume: self.volume_settings.sfx_user_setting,
             music_volume: self.volume_settings.music_user_setting,
             resolution: self.display_settings.resolution,
             fullscreen: self.display_settings.fullscreen,
-            display_first_time_help: *display_first_time_help,
-        };
-
-        self.user_settings
-            .persist()
-            .expect("failed to persist user supplied settings");
-    }
-    #[allow(dead_code)]
-    pub fn register_introductory_help(&mut self) {
-        //AFAICT this is a false positive on this lint error
-        #[allow(unused_mut)]
-        let mut da_settings = self.user_settings.get_mut();
-        let UserSettings::V1 {
-            global_volume,
-            sfx_volume,
-            music_volume,
-            fullscreen,
-            resolution,
-            ..
-        } = da_settings;
-        *da_settings = UserSettings::V1 {
-            global_volume: *global_volume,
-            sfx_volume: *sfx_volume,
-            music_volume: *music_volume,
-            resolution: *resolution,
-            fullscreen: *fullscreen,
-            display_first_time_help: false,
+            display_first_time_help,
+            controls: self.control_bindings.clone(),
+            camera: self.camera_settings.clone(),
         };
 
         self.user_settings
@@ -350,4 +474,6 @@ fn load_app_settings(mut commands: Commands, mut user_settings: ResMut<Persisten
     user_settings.persist().expect("settings migration error");
     commands.insert_resource::<VolumeSettings>(user_settings.volume_settings());
     commands.insert_resource::<DisplaySettings>(user_settings.display_settings());
+    commands.insert_resource::<ControlBindings>(user_settings.control_bindings());
+    commands.insert_resource::<CameraSettings>(user_settings.camera_settings());
 }
@@ -0,0 +1,147 @@
+use std::{
+    f32::consts::{PI, TAU},
+    time::Duration,
+};
+
+use bevy::prelude::*;
+
+use crate::{gametimer::GameClock, SimState};
+
+pub struct CalendarPlugin;
+
+impl Plugin for CalendarPlugin {
+    fn build(&self, app: &mut App) {
+        app.insert_resource(SimCalendar::default())
+            .add_systems(
+                OnEnter(SimState::Playing),
+                spawn_night_overlay.run_if(run_once()),
+            )
+            .add_systems(Update, (advance_calendar, tint_night).chain());
+    }
+}
+
+// Sim-seconds - at the default tick rate a day lasts a couple of real minutes.
+const DAY_LENGTH: f32 = 480.0;
+const DAYS_PER_SEASON: u32 = 4;
+// Fraction of the day that's light out, centered on noon.
+const DAYLIGHT_FRACTION: f32 = 0.6;
+const NIGHT_MAX_ALPHA: f32 = 0.55;
+const NIGHT_FORAGER_FACTOR: f32 = 0.4;
+const NIGHT_OVERLAY_SIZE: f32 = 10000.0;
+const FIRST_LIGHT: f32 = 0.3;
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Season {
+    Spring,
+    Summer,
+    Autumn,
+    Winter,
+}
+impl Season {
+    // Scales how fast food grows and turns up in the world.
+    pub fn food_factor(&self) -> f32 {
+        match self {
+            Season::Spring => 1.5,
+            Season::Summer => 1.0,
+            Season::Autumn => 0.6,
+            Season::Winter => 0.0,
+        }
+    }
+}
+
+#[derive(Resource)]
+pub struct SimCalendar {
+    elapsed: Duration,
+}
+impl Default for SimCalendar {
+    // The first day starts in the morning, rather than dropping a new player into the dark.
+    fn default() -> Self {
+        SimCalendar {
+            elapsed: Duration::from_secs_f32(DAY_LENGTH * FIRST_LIGHT),
+        }
+    }
+}
+impl SimCalendar {
+    pub fn elapsed(&self) -> Duration {
+        self.elapsed
+    }
+    fn days(&self) -> f32 {
+        self.elapsed.as_secs_f32() / DAY_LENGTH
+    }
+    // Counting from day 1, like a normal person.
+    pub fn day(&self) -> u32 {
+        self.days() as u32 + 1
+    }
+    pub fn day_of_season(&self) -> u32 {
+        self.days() as u32 % DAYS_PER_SEASON + 1
+    }
+    pub fn season(&self) -> Season {
+        match (self.days() as u32 / DAYS_PER_SEASON) % 4 {
+            0 => Season::Spring,
+            1 => Season::Summer,
+            2 => Season::Autumn,
+            _ => Season::Winter,
+        }
+    }
+    // 0.0 is midnight, 0.5 is noon.
+    pub fn time_of_day(&self) -> f32 {
+        self.days().fract()
+    }
+    pub fn clock_time(&self) -> (u32, u32) {
+        let minutes = (self.time_of_day() * 24.0 * 60.0) as u32;
+        (minutes / 60, minutes % 60)
+    }
+    // 1.0 in full daylight, 0.0 in the dead of night, with a smooth dusk and dawn in between.
+    pub fn daylight(&self) -> f32 {
+        let sun = -(self.time_of_day() * TAU).cos();
+        let horizon = (DAYLIGHT_FRACTION * PI).cos();
+        ((sun - horizon) * 4.0 + 0.5).clamp(0.0, 1.0)
+    }
+    pub fn is_night(&self) -> bool {
+        self.daylight() < 0.5
+    }
+    // Fewer ants go out foraging in the dark.
+    pub fn forager_activity(&self) -> f32 {
+        NIGHT_FORAGER_FACTOR + (1.0 - NIGHT_FORAGER_FACTOR) * self.daylight()
+    }
+}
+
+#[derive(Component)]
+struct NightOverlay;
+
+// Run condition for anything that makes new food - nothing grows in winter.
+pub fn food_in_season(calendar: Res<SimCalendar>) -> bool {
+    calendar.season().food_factor() > 0.0
+}
+
+fn advance_calendar(clock: Res<GameClock>, mut calendar: ResMut<SimCalendar>) {
+    let season = calendar.season();
+    calendar.elapsed += clock.delta;
+    if calendar.season() != season {
+        info!("{:?} has arrived", calendar.season());
+    }
+}
+
+fn spawn_night_overlay(mut commands: Commands) {
+    commands.spawn((
+        SpriteBundle {
+            sprite: Sprite {
+                color: Color::rgba_u8(8, 10, 40, 0),
+                custom_size: Some(Vec2::splat(NIGHT_OVERLAY_SIZE)),
+                ..default()
+            },
+            transform: Transform::from_xyz(0.0, 0.0, 50.0),
+            ..default()
+        },
+        NightOverlay,
+        Name::new("Night Overlay"),
+    ));
+}
+
+fn tint_night(calendar: Res<SimCalendar>, mut q: Query<&mut Sprite, With<NightOverlay>>) {
+    for mut sprite in q.iter_mut() {
+        sprite
+            .color
+            .set_a((1.0 - calendar.daylight()) * NIGHT_MAX_ALPHA);
+    }
+}
@@ -1,16 +1,28 @@
 use std::{f32::consts::TAU, marker::PhantomData};
 
-use bevy::{prelude::*, utils::HashMap};
+use bevy::{ecs::system::SystemParam, prelude::*, utils::HashMap};
 use bevy_prng::WyRand;
 use bevy_rand::resource::GlobalEntropy;
+use leafwing_input_manager::prelude::ActionState;
 use rand::Rng;
 
 use crate::{
-    ant::{Ant, AntCommandsExt, AntSettings, ForagerAnt, IdleAnt, NursemaidAnt},
+    ant::{
+        Ant, AntCommandsExt, AntSettings, ForagerAnt, IdleAnt, NursemaidAnt, Starving,
+        ANT_DROP_OFF_RADIUS,
+    },
+    calendar::SimCalendar,
     food::FoodQuant,
-    gizmodable::{GizmoDrawOp, VisualDebug},
+    gametimer::GameClock,
+    gizmodable::{
+        debug_layer_enabled, DebugLayer, DebugLayerAppExt, GizmoDrawOp, GizmoSystemSet, VisualDebug,
+    },
     larva::LarvaSettings,
-    UIFocus,
+    playerinput::{CursorWorldPos, GamefieldActions},
+    terrain::{clear_of_obstacles, ObstacleQuery},
+    weather::Weather,
+    worldgen::WorldBounds,
+    SimState, UIFocus,
 };
 
 pub struct ColonyPlugin;
@@ -33,10 +45,12 @@ impl UpgradeStringIndex {
 }
 
 const STARTING_ANT_CAP: i32 = 35;
+// About eight sim-minutes of upkeep for the starting ants and the queen, so nobody goes hungry before the first foragers get back.
+const STARTING_FOOD: i32 = 90;
 #[derive(Component)]
 pub struct Colony;
 #[derive(Component)]
-pub struct ColonyPos(Vec2);
+pub struct ColonyPos(pub Vec2);
 #[derive(Reflect, Component)]
 pub struct AntCapacity(pub i32);
 #[derive(Component, Reflect)]
@@ -51,6 +65,74 @@ pub struct MaxFood(pub i32);
 #[derive(Component)]
 pub struct StartingAnts(i32);
 
+const ENTRANCE_FOOD_COST: i32 = 100;
+const ENTRANCE_MIN_SPACING: f32 = 100.0;
+const ENTRANCE_RADIUS: f32 = 12.0;
+const ENTRANCE_SCALE: f32 = 0.5;
+
+// Somewhere foragers can drop food off. Every colony starts with one on top of the nest, and the player can dig more.
+#[derive(Component)]
+pub struct NestEntrance {
+    pub colony: Entity,
+}
+
+pub type EntranceQuery<'w, 's> = Query<'w, 's, (&'static GlobalTransform, &'static NestEntrance)>;
+
+pub fn nearest_entrance(entrances: &EntranceQuery, colony: Entity, pos: Vec2) -> Option<Vec2> {
+    entrances
+        .iter()
+        .filter(|(_, entrance)| entrance.colony == colony)
+        .map(|(transform, _)| transform.translation().truncate())
+        .min_by(|a, b| a.distance(pos).total_cmp(&b.distance(pos)))
+}
+
+// New entrances have to go somewhere inside the world, on open ground, and not right on top of another one.
+#[derive(SystemParam)]
+pub struct EntranceSite<'w, 's> {
+    entrances: EntranceQuery<'w, 's>,
+    obstacles: ObstacleQuery<'w, 's>,
+    bounds: Res<'w, WorldBounds>,
+}
+impl<'w, 's> EntranceSite<'w, 's> {
+    fn can_build(&self, pos: Vec2) -> bool {
+        self.bounds.contains(pos)
+            && clear_of_obstacles(&self.obstacles, pos, ENTRANCE_RADIUS)
+            && self.entrances.iter().all(|(transform, _)| {
+                transform.translation().truncate().distance(pos) >= ENTRANCE_MIN_SPACING
+            })
+    }
+}
+
+// Food that's been eaten or gone off but not yet taken out of the larder - we only ever take whole units of food.
+#[derive(Component, Default)]
+pub struct FoodUpkeep {
+    eaten: f32,
+    spoiled: f32,
+    // Units owed that the larder couldn't cover, since the colony was last fed in full.
+    shortfall: i32,
+}
+
+#[derive(Resource, Reflect)]
+#[reflect(Resource)]
+pub struct UpkeepSettings {
+    pub food_per_ant_per_minute: f32,
+    pub spoilage: bool,
+    // Anything stored above this fraction of MaxFood starts to rot.
+    pub spoil_threshold: f32,
+    // Fraction of the surplus lost per sim-minute.
+    pub spoil_rate: f32,
+}
+impl Default for UpkeepSettings {
+    fn default() -> Self {
+        UpkeepSettings {
+            food_per_ant_per_minute: 0.3,
+            spoilage: true,
+            spoil_threshold: 0.75,
+            spoil_rate: 0.2,
+        }
+    }
+}
+
 #[derive(Component, Default)]
 pub struct LaborData<T: Component + Default> {
     marker: PhantomData<T>,
@@ -75,6 +157,7 @@ pub struct ColonyData {
     target_number_of_larva: LarvaTarget,
     max_food: MaxFood,
     home: ColonyPos,
+    upkeep: FoodUpkeep,
 }
 
 #[derive(SystemSet, Hash, Debug, PartialEq, Eq, Clone)]
@@ -89,6 +172,9 @@ impl Plugin for ColonyPlugin {
         app.register_type::<AntPopulation>()
             .register_type::<AntCapacity>()
             .register_type::<MaxFood>()
+            .register_type::<UpkeepSettings>()
+            .insert_resource(UpkeepSettings::default())
+            .register_debug_layer(DebugLayer::Colony)
             .add_systems(Startup, init_default_colony)
             .add_systems(
                 OnEnter(UIFocus::Gamefield),
@@ -108,6 +194,11 @@ impl Plugin for ColonyPlugin {
                 (
                     labor_census.in_set(LaborPhase::TakeCensus),
                     request_nursemaids.before(LaborPhase::TakeCensus),
+                    (colony_upkeep, spoil_food).run_if(in_state(SimState::Playing)),
+                    build_nest_entrance.run_if(in_state(UIFocus::Gamefield)),
+                    colony_debug
+                        .run_if(debug_layer_enabled(DebugLayer::Colony))
+                        .before(GizmoSystemSet::GizmoQueueDraw),
                 ),
             );
     }
@@ -119,22 +210,44 @@ pub fn init_default_colony(mut commands: Commands) {
             col: Colony,
             ant_cap: AntCapacity(STARTING_ANT_CAP),
             ant_pop: AntPopulation(0),
-            food: FoodQuant::empty(),
+            food: FoodQuant(STARTING_FOOD),
             max_food: MaxFood(200),
             target_number_of_larva: LarvaTarget(1),
             labor_stats: LaborStats::default(),
             home: ColonyPos((0., 0.).into()),
+            upkeep: FoodUpkeep::default(),
         },
         StartingAnts(25),
         UpgradeStringIndex::new(),
-        VisualDebug::from_persistent(GizmoDrawOp::circle(Vec2::ZERO, 30.0, Color::YELLOW)),
+        VisualDebug::default(),
         Name::new("Player_Colony"),
     ));
 }
 
+// The colony's home, and every entrance foragers can deliver to.
+fn colony_debug(
+    mut col_q: Query<(Entity, &ColonyPos, &mut VisualDebug), With<Colony>>,
+    entrances: EntranceQuery,
+) {
+    for (colony, home, mut dbg) in col_q.iter_mut() {
+        dbg.add(GizmoDrawOp::circle(home.0, 30.0, Color::YELLOW));
+        for (transform, _) in entrances
+            .iter()
+            .filter(|(_, entrance)| entrance.colony == colony)
+        {
+            dbg.add(GizmoDrawOp::circle(
+                transform.translation().truncate(),
+                ANT_DROP_OFF_RADIUS,
+                Color::ORANGE,
+            ));
+        }
+    }
+}
+
 fn labor_census(
     q: Query<(Option<&ForagerAnt>, Option<&NursemaidAnt>, Option<&IdleAnt>), With<Ant>>,
     ant_settings: Res<AntSettings>,
+    calendar: Res<SimCalendar>,
     mut col_q: Query<
         (
             &mut LaborData<ForagerAnt>,
@@ -159,7 +272,8 @@ fn labor_census(
     idle_stats.active = idle;
     nursemaid_stats.active = nursemaids;
     forager_stats.active = foragers;
-    forager_stats.requested = (max_food.0 - food.0) / ant_settings.carry_capacity;
+    let wanted = (max_food.0 - food.0) / ant_settings.carry_capacity;
+    forager_stats.requested = (wanted as f32 * calendar.forager_activity()).round() as i32;
 }
 
 fn request_nursemaids(
@@ -176,12 +290,16 @@ fn spawn_starting_ants(
     assets: Res<AssetServer>,
     q: Query<(Entity, &StartingAnts)>,
 ) {
-    commands.spawn(SpriteBundle {
-        texture: assets.load("ant_hill.png"),
-        transform: Transform::from_xyz(0., 0., 0.1),
-        ..default()
-    });
     for (ent, starting_ants) in q.iter() {
+        commands.spawn((
+            SpriteBundle {
+                texture: assets.load("ant_hill.png"),
+                transform: Transform::from_xyz(0., 0., 0.1),
+                ..default()
+            },
+            NestEntrance { colony: ent },
+            Name::new("Nest"),
+        ));
         for _ in 1..starting_ants.0 {
             let offset_vec = random_offset_vec(&mut rng);
             let ant_pos = Vec2::ZERO + offset_vec;
@@ -197,3 +315,108 @@ fn random_offset_vec(rng: &mut ResMut<GlobalEntropy<WyRand>>) -> Vec2 {
     offset_vec *= rng.gen_range(2.0..5.0);
     offset_vec
 }
+
+// Every adult eats, whether they're working or not. If the larder runs dry, the whole colony starts to starve.
+fn colony_upkeep(
+    mut commands: Commands,
+    mut rng: ResMut<GlobalEntropy<WyRand>>,
+    clock: Res<GameClock>,
+    settings: Res<UpkeepSettings>,
+    weather: Res<Weather>,
+    mut col_q: Query<(Entity, &mut FoodQuant, &mut FoodUpkeep, &AntPopulation), With<Colony>>,
+    ant_q: Query<(Entity, &Ant, Has<Starving>)>,
+) {
+    let minutes = clock.delta.as_secs_f32() / 60.0;
+    for (colony, mut food, mut upkeep, ant_pop) in col_q.iter_mut() {
+        upkeep.eaten +=
+            ant_pop.0 as f32 * settings.food_per_ant_per_minute * weather.upkeep_factor() * minutes;
+        let owed = upkeep.eaten.floor() as i32;
+        if owed <= 0 {
+            continue;
+        }
+        upkeep.eaten -= owed as f32;
+        upkeep.shortfall = if food.0 >= owed {
+            0
+        } else {
+            upkeep.shortfall + owed - food.0
+        };
+        food.0 = (food.0 - owed).max(0);
+        // One missed unit is a lean moment rather than a famine.
+        let hungry = upkeep.shortfall > 1;
+
+        for (entity, _, starving) in ant_q.iter().filter(|(_, ant, _)| ant.colony() == colony) {
+            match (hungry, starving) {
+                (false, true) => {
+                    commands.entity(entity).remove::<Starving>();
+                }
+                (true, false) => {
+                    commands.entity(entity).insert(Starving::random(&mut *rng));
+                }
+                _ => {}
+            }
+        }
+    }
+}
+
+fn spoil_food(
+    clock: Res<GameClock>,
+    settings: Res<UpkeepSettings>,
+    mut col_q: Query<(&mut FoodQuant, &mut FoodUpkeep, &MaxFood), With<Colony>>,
+) {
+    if !settings.spoilage {
+        return;
+    }
+    let minutes = clock.delta.as_secs_f32() / 60.0;
+    for (mut food, mut upkeep, max_food) in col_q.iter_mut() {
+        let surplus = food.0 as f32 - max_food.0 as f32 * settings.spoil_threshold;
+        if surplus <= 0.0 {
+            upkeep.spoiled = 0.0;
+            continue;
+        }
+        upkeep.spoiled += surplus * settings.spoil_rate * minutes;
+        let spoiled = upkeep.spoiled.floor() as i32;
+        if spoiled > 0 {
+            upkeep.spoiled -= spoiled as f32;
+            food.0 -= spoiled;
+        }
+    }
+}
+
+fn build_nest_entrance(
+    mut commands: Commands,
+    assets: Res<AssetServer>,
+    actions: Query<&ActionState<GamefieldActions>>,
+    cursor: CursorWorldPos,
+    site: EntranceSite,
+    mut col_q: Query<(Entity, &mut FoodQuant), With<Colony>>,
+) {
+    if !actions
+        .iter()
+        .any(|action| action.just_pressed(GamefieldActions::BuildEntrance))
+    {
+        return;
+    }
+    let (Some(pos), Ok((colony, mut food))) = (cursor.get(), col_q.get_single_mut()) else {
+        return;
+    };
+    if food.0 < ENTRANCE_FOOD_COST {
+        info!("not enough food to dig a new entrance");
+        return;
+    }
+    if !site.can_build(pos) {
+        info!("can't dig a new entrance at {:?}", pos);
+        return;
+    }
+    food.0 -= ENTRANCE_FOOD_COST;
+    let mut transform = Transform::from_xyz(pos.x, pos.y, 0.1);
+    transform.scale = Vec3::new(ENTRANCE_SCALE, ENTRANCE_SCALE, 1.0);
+    commands.spawn((
+        SpriteBundle {
+            texture: assets.load("ant_hill.png"),
+            transform,
+            ..default()
+        },
+        NestEntrance { colony },
+        Name::new("Nest Entrance"),
+    ));
+}
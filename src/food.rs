@@ -1,15 +1,25 @@
 use std::{f32::consts::TAU, time::Duration};
 
-use bevy::{ecs::query::Has, math::Vec3Swizzles, prelude::*};
+use bevy::{
+    ecs::{query::Has, system::SystemParam},
+    math::Vec3Swizzles,
+    prelude::*,
+    sprite::MaterialMesh2dBundle,
+};
 use bevy_prng::WyRand;
 use bevy_rand::resource::GlobalEntropy;
 use rand::prelude::*;
 
 use crate::{
     ant::Carried,
+    calendar::{food_in_season, SimCalendar},
     colony::{Colony, MaxFood},
-    gametimer::SimTimer,
+    gametimer::{GameClock, SimTimer},
     misc_utility::remap,
+    nav::DistanceAwareQuery,
+    terrain::{clear_of_obstacles, ObstacleQuery, OBSTACLE_CLEARANCE},
+    transport::{spawn_heavy_food, HeavyFood, HEAVY_FOOD_CHANCE, HEAVY_FOOD_PER_CARRIER},
+    worldgen::WorldBounds,
     SimState, SoundScape, SpatialMarker,
 };
 
@@ -19,6 +29,7 @@ impl Plugin for FoodPlugin {
     fn build(&self, app: &mut App) {
         app.register_type::<FoodQuant>()
             .add_event::<FoodDeltaEvent>()
+            .init_resource::<FoodProduction>()
             .add_systems(
                 OnEnter(SimState::Playing),
                 spawn_first_chunk.run_if(run_once()),
@@ -27,6 +38,7 @@ impl Plugin for FoodPlugin {
                 Update,
                 (
                     freebie_food_spawn.run_if(on_food_timer),
+                    (regrow_food_bushes, bush_drop_food.run_if(food_in_season)).chain(),
                     scale_food,
                     apply_sprite_to_carried,
                 ),
@@ -35,7 +47,7 @@ impl Plugin for FoodPlugin {
     }
 }
 
-#[derive(Component, Reflect)]
+#[derive(Component, Reflect, Clone, Copy)]
 pub struct FoodQuant(pub i32);
 impl FoodQuant {
     fn take_food(&mut self, to_quant: &mut FoodQuant, requested: i32, max: Option<i32>) {
@@ -69,6 +81,28 @@ pub struct FoodDeltaEvent {
 #[derive(Component)]
 pub struct FoodSpawnTimer;
 
+// A fixed food source - periodically drops chunks around itself until there are `max_chunks` lying nearby.
+// Every chunk comes out of the bush's own stock, which grows back fastest when the bush is about half picked over.
+// Strip it bare and it will take a long time to recover.
+#[derive(Component)]
+pub struct FoodBush {
+    pub max_chunks: usize,
+    pub stock: f32,
+    pub capacity: f32,
+}
+impl FoodBush {
+    fn regrowth_per_sec(&self) -> f32 {
+        let fullness = self.stock / self.capacity;
+        BUSH_REGROWTH_RATE * self.stock * (1.0 - fullness) + BUSH_REGROWTH_FLOOR * (1.0 - fullness)
+    }
+}
+
+// How much food the world's bushes are growing back right now, per sim-minute.
+#[derive(Resource, Default)]
+pub struct FoodProduction {
+    pub per_minute: f32,
+}
+
 const FOOD_SPAWN_MIN_DIST: f32 = 80.0;
 const FOOD_SPAWN_MAX_DIST: f32 = 600.0;
 const FOOD_CHUNK_MAX_STARTING_AMOUNT: i32 = 1800;
@@ -77,6 +111,14 @@ const FREEBIE_FOOD_CAP: usize = 200;
 const FREEBIE_FOOD_INTERVAL: u64 = 15;
 const BASELINE_EXCLUSION_DISTANCE: f32 = 65.0;
 const FOOD_MIN_SCALE: f32 = 0.125;
+const BUSH_DROP_INTERVAL: u64 = 20;
+const BUSH_DROP_MIN_DIST: f32 = 15.0;
+const BUSH_DROP_MAX_DIST: f32 = 35.0;
+const BUSH_RADIUS: f32 = 8.0;
+const BUSH_CAPACITY: f32 = 1500.0;
+const BUSH_REGROWTH_RATE: f32 = 4.0 / BUSH_CAPACITY;
+const BUSH_REGROWTH_FLOOR: f32 = 0.05;
+const BUSH_MIN_SCALE: f32 = 0.5;
 
 fn on_food_timer(q: Query<&SimTimer, With<FoodSpawnTimer>>) -> bool {
     if let Ok(food_timer) = q.get_single() {
@@ -86,34 +128,52 @@ fn on_food_timer(q: Query<&SimTimer, With<FoodSpawnTimer>>) -> bool {
     }
 }
 
+// Anywhere we drop new food has to be somewhere an ant can actually get to - clear of obstacles, and not so close to the edge that ants get steered away from it.
+#[derive(SystemParam)]
+pub struct FoodPlacement<'w, 's> {
+    obstacles: ObstacleQuery<'w, 's>,
+    bounds: Res<'w, WorldBounds>,
+}
+impl<'w, 's> FoodPlacement<'w, 's> {
+    pub fn can_place(&self, pos: Vec2, food_q: &FoodQuant) -> bool {
+        self.bounds.interior().contains(pos)
+            && clear_of_obstacles(
+                &self.obstacles,
+                pos,
+                food_q.interaction_distance() + OBSTACLE_CLEARANCE,
+            )
+    }
+}
+
 fn freebie_food_spawn(
     mut commands: Commands,
     mut sounds: EventWriter<SoundScape>,
     assets: Res<AssetServer>,
     mut rng: ResMut<GlobalEntropy<WyRand>>,
     q: Query<(&GlobalTransform, &FoodQuant), Without<Carried>>,
+    placement: FoodPlacement,
+    calendar: Res<SimCalendar>,
 ) {
-    if q.iter().len() >= FREEBIE_FOOD_CAP {
+    // The plants are the world's real larder - the only thing that turns up out of nowhere is the odd dead beetle.
+    let chance = (HEAVY_FOOD_CHANCE * calendar.season().food_factor() as f64).min(1.0);
+    if q.iter().len() >= FREEBIE_FOOD_CAP || !rng.gen_bool(chance) {
         return;
     }
     let mut pos: Option<Vec2> = None;
     let mut iters: u8 = 0;
-    let food_q = FoodQuant(
-        rng.gen_range(FOOD_CHUNK_MIN_STARTING_AMOUNT / 20..FOOD_CHUNK_MAX_STARTING_AMOUNT / 20)
-            * 20,
-    );
-
-    let food_scale = food_q.0 as f32 / FOOD_CHUNK_MAX_STARTING_AMOUNT as f32;
+    let carriers_needed = rng.gen_range(2..=4);
+    let food_q = FoodQuant(carriers_needed as i32 * HEAVY_FOOD_PER_CARRIER);
 
     while pos.is_none() && iters < 50 {
         iters += 1;
         let random_angle = rng.gen_range(0.0..TAU);
         let try_pos = Vec2::from((random_angle.cos(), random_angle.sin()))
             * rng.gen_range(FOOD_SPAWN_MIN_DIST..FOOD_SPAWN_MAX_DIST);
-        pos = if q.iter().all(|(xform, quant)| {
+        let clear_of_food = q.iter().all(|(xform, quant)| {
             xform.translation().xy().distance(try_pos)
                 >= (food_q.exclusion_distance() + quant.exclusion_distance())
-        }) {
+        });
+        pos = if clear_of_food && placement.can_place(try_pos, &food_q) {
             Some(try_pos)
         } else {
             None
@@ -121,20 +181,117 @@ fn freebie_food_spawn(
     }
 
     if let Some(food_pos) = pos {
-        let texture = assets.load("food_chunk.png");
         sounds.send(SoundScape::FoodSpawn);
-        let mut transform = Transform::from_xyz(food_pos.x, food_pos.y, 0.1);
-        transform.scale = Vec3::from((food_scale, food_scale, 1.0));
-        commands.spawn((
+        spawn_heavy_food(&mut commands, &assets, food_pos, carriers_needed);
+    }
+}
+
+pub fn spawn_food_chunk(
+    commands: &mut Commands,
+    assets: &AssetServer,
+    pos: Vec2,
+    food_q: FoodQuant,
+) -> Entity {
+    let food_scale = food_q.0 as f32 / FOOD_CHUNK_MAX_STARTING_AMOUNT as f32;
+    let texture = assets.load("food_chunk.png");
+    let mut transform = Transform::from_xyz(pos.x, pos.y, 0.1);
+    transform.scale = Vec3::from((food_scale, food_scale, 1.0));
+    commands
+        .spawn((
             food_q,
             SpriteBundle {
                 texture,
-                transform: transform,
+                transform,
                 ..default()
             },
             SpatialMarker,
-        ));
+        ))
+        .id()
+}
+
+pub fn spawn_food_bush(
+    commands: &mut Commands,
+    meshes: &mut Assets<Mesh>,
+    materials: &mut Assets<ColorMaterial>,
+    pos: Vec2,
+    max_chunks: usize,
+) -> Entity {
+    commands
+        .spawn((
+            MaterialMesh2dBundle {
+                mesh: meshes.add(shape::Circle::new(BUSH_RADIUS).into()).into(),
+                material: materials.add(ColorMaterial::from(Color::rgb_u8(75, 105, 47))),
+                transform: Transform::from_xyz(pos.x, pos.y, 0.08),
+                ..default()
+            },
+            FoodBush {
+                max_chunks,
+                stock: BUSH_CAPACITY,
+                capacity: BUSH_CAPACITY,
+            },
+            SimTimer {
+                time: Timer::new(Duration::new(BUSH_DROP_INTERVAL, 0), TimerMode::Repeating),
+            },
+            Name::new("Food Bush"),
+        ))
+        .id()
+}
+
+fn bush_drop_food(
+    mut commands: Commands,
+    mut sounds: EventWriter<SoundScape>,
+    assets: Res<AssetServer>,
+    mut rng: ResMut<GlobalEntropy<WyRand>>,
+    mut bushes: Query<(&GlobalTransform, &mut FoodBush, &SimTimer)>,
+    food: DistanceAwareQuery<SpatialMarker, &FoodQuant, (Without<Carried>, Without<Colony>)>,
+    placement: FoodPlacement,
+) {
+    for (transform, mut bush, timer) in bushes.iter_mut() {
+        if !timer.time.finished() {
+            continue;
+        }
+        let bush_pos = transform.translation().xy();
+        if food.within_distance(bush_pos, BUSH_DROP_MAX_DIST).count() >= bush.max_chunks {
+            continue;
+        }
+        let rolled = rng
+            .gen_range(FOOD_CHUNK_MIN_STARTING_AMOUNT / 20..FOOD_CHUNK_MAX_STARTING_AMOUNT / 40)
+            * 20;
+        let food_q = FoodQuant(rolled.min(bush.stock as i32 / 20 * 20));
+        if food_q.0 < FOOD_CHUNK_MIN_STARTING_AMOUNT / 2 {
+            continue;
+        }
+        for _ in 0..10 {
+            let try_pos = bush_pos
+                + Vec2::from_angle(rng.gen_range(0.0..TAU))
+                    * rng.gen_range(BUSH_DROP_MIN_DIST..BUSH_DROP_MAX_DIST);
+            if placement.can_place(try_pos, &food_q) {
+                sounds.send(SoundScape::FoodSpawn);
+                bush.stock -= food_q.0 as f32;
+                spawn_food_chunk(&mut commands, &assets, try_pos, food_q);
+                break;
+            }
+        }
+    }
+}
+
+fn regrow_food_bushes(
+    clock: Res<GameClock>,
+    calendar: Res<SimCalendar>,
+    mut production: ResMut<FoodProduction>,
+    mut bushes: Query<(&mut FoodBush, &mut Transform)>,
+) {
+    let delta = clock.delta.as_secs_f32();
+    let season_factor = calendar.season().food_factor();
+    let mut per_sec = 0.0;
+    for (mut bush, mut transform) in bushes.iter_mut() {
+        let growth = bush.regrowth_per_sec() * season_factor;
+        per_sec += growth;
+        bush.stock = (bush.stock + growth * delta).min(bush.capacity);
+        let scale = remap(0.0, 1.0, BUSH_MIN_SCALE, 1.0, bush.stock / bush.capacity);
+        transform.scale = Vec3::new(scale, scale, 1.0);
     }
+    production.per_minute = per_sec * 60.0;
 }
 
 fn spawn_first_chunk(
@@ -144,16 +301,12 @@ fn spawn_first_chunk(
 ) {
     let random_angle = rng.gen_range(0.0..TAU);
     let pos = Vec2::from((random_angle.cos(), random_angle.sin())) * FOOD_SPAWN_MIN_DIST;
-    let texture = assets.load("food_chunk.png");
-    commands.spawn((
+    spawn_food_chunk(
+        &mut commands,
+        &assets,
+        pos,
         FoodQuant(FOOD_CHUNK_MAX_STARTING_AMOUNT),
-        SpriteBundle {
-            texture,
-            transform: Transform::from_xyz(pos.x, pos.y, 0.1),
-            ..default()
-        },
-        SpatialMarker,
-    ));
+    );
     commands.spawn((
         SimTimer {
             time: Timer::new(
@@ -165,7 +318,12 @@ fn spawn_first_chunk(
     ));
 }
 
-fn scale_food(mut q: Query<(&mut Transform, &FoodQuant), (With<Sprite>, Without<Carried>)>) {
+fn scale_food(
+    mut q: Query<
+        (&mut Transform, &FoodQuant),
+        (With<Sprite>, Without<Carried>, Without<HeavyFood>),
+    >,
+) {
     q.iter_mut().for_each(|(mut transform, quant)| {
         let mut scale = quant.0 as f32 / FOOD_CHUNK_MAX_STARTING_AMOUNT as f32;
         scale = remap(0.0, 1.0, FOOD_MIN_SCALE, 1.0, scale);
@@ -43,6 +43,7 @@ impl Plugin for GameTimerPlugin {
             .add_state::<TickRate>()
             .add_systems(OnEnter(SimState::Playing), start_sim)
             .add_systems(OnEnter(SimState::Paused), pause_sim)
+            .add_systems(OnEnter(SimState::GameOver), pause_sim)
             .add_systems(PreUpdate, tick_sim_timers);
     }
 }
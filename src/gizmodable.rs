@@ -4,26 +4,24 @@ pub struct Gizmotastic;
 
 impl Plugin for Gizmotastic {
     fn build(&self, app: &mut App) {
-        app.insert_resource::<DebugGizmosOn>(DebugGizmosOn {
-            root: false,
-            debug_systems: HashMap::new(),
-        })
-        .configure_sets(
-            Update,
-            (
-                GizmoSystemSet::GizmoQueueDraw.run_if(debug_gizmos_enabled),
-                GizmoSystemSet::GizmoClear,
+        app.init_resource::<DebugGizmosOn>()
+            .configure_sets(
+                Update,
+                (
+                    GizmoSystemSet::GizmoQueueDraw.run_if(debug_gizmos_enabled),
+                    GizmoSystemSet::GizmoClear,
+                )
+                    .chain(),
             )
-                .chain(),
-        )
-        .register_type::<DebugGizmosOn>()
-        .add_systems(
-            Update,
-            (
-                render_gizmos.in_set(GizmoSystemSet::GizmoQueueDraw),
-                clear_gizmos.in_set(GizmoSystemSet::GizmoClear),
-            ),
-        );
+            .register_type::<DebugGizmosOn>()
+            .add_systems(
+                Update,
+                (
+                    render_gizmos.in_set(GizmoSystemSet::GizmoQueueDraw),
+                    clear_gizmos.in_set(GizmoSystemSet::GizmoClear),
+                    clear_debug_labels.run_if(not(debug_gizmos_enabled)),
+                ),
+            );
     }
 }
 
@@ -38,17 +36,78 @@ impl DebugGizmosOn {
     pub fn render_enabled(&self) -> bool {
         self.root
     }
+    pub fn toggle_render(&mut self) {
+        self.root = !self.root;
+    }
     pub fn system_enabled(&self, k: &str) -> bool {
-        self.root && self.debug_systems.get(k).is_some()
+        self.root && self.layer_enabled(k)
+    }
+    // Whether the layer itself is switched on, regardless of the root toggle.
+    pub fn layer_enabled(&self, k: &str) -> bool {
+        self.debug_systems.get(k).copied().unwrap_or(false)
     }
     pub fn register_system(&mut self, k: &str) {
-        self.debug_systems.insert(k.clone().to_string(), false);
+        self.debug_systems.insert(k.to_string(), false);
+    }
+    pub fn toggle_system(&mut self, k: &str) {
+        if let Some(enabled) = self.debug_systems.get_mut(k) {
+            *enabled = !*enabled;
+        }
+    }
+    // Registered layers in a stable order, along with whether each one is switched on.
+    pub fn systems(&self) -> Vec<(String, bool)> {
+        let mut systems = self
+            .debug_systems
+            .iter()
+            .map(|(k, enabled)| (k.clone(), *enabled))
+            .collect::<Vec<(String, bool)>>();
+        systems.sort();
+        systems
     }
 }
 fn debug_gizmos_enabled(g: Res<DebugGizmosOn>) -> bool {
     g.render_enabled()
 }
 
+// Groups of debug drawing that can be switched on and off separately from the debug menu.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum DebugLayer {
+    Nav,
+    Roles,
+    ForagerState,
+    Drift,
+    Colony,
+}
+impl DebugLayer {
+    pub fn name(&self) -> &'static str {
+        match self {
+            DebugLayer::Nav => "nav",
+            DebugLayer::Roles => "roles",
+            DebugLayer::ForagerState => "forager state",
+            DebugLayer::Drift => "drift",
+            DebugLayer::Colony => "colony",
+        }
+    }
+}
+
+// Run condition for debug systems - they don't run at all unless their layer, and gizmos as a whole, are switched on.
+pub fn debug_layer_enabled(layer: DebugLayer) -> impl FnMut(Res<DebugGizmosOn>) -> bool + Clone {
+    move |g: Res<DebugGizmosOn>| g.system_enabled(layer.name())
+}
+
+pub trait DebugLayerAppExt {
+    fn register_debug_layer(&mut self, layer: DebugLayer) -> &mut Self;
+}
+impl DebugLayerAppExt for App {
+    fn register_debug_layer(&mut self, layer: DebugLayer) -> &mut Self {
+        self.init_resource::<DebugGizmosOn>();
+        self.world
+            .resource_mut::<DebugGizmosOn>()
+            .register_system(layer.name());
+        self
+    }
+}
+
 #[derive(SystemSet, Debug, Hash, Eq, PartialEq, Clone, Copy)]
 pub enum GizmoSystemSet {
     GizmoQueueDraw,
@@ -73,16 +132,43 @@ pub struct RectArgs {
     size: Vec2,
     color: Color,
 }
+pub struct PolylineArgs {
+    points: Vec<Vec2>,
+    color: Color,
+}
+pub struct ArcArgs {
+    position: Vec2,
+    direction_angle: f32,
+    arc_angle: f32,
+    radius: f32,
+    color: Color,
+}
+pub struct TextArgs {
+    position: Vec2,
+    text: String,
+    color: Color,
+}
+
+const ARROW_HEAD_LENGTH: f32 = 3.0;
+const ARROW_HEAD_ANGLE: f32 = 0.5;
+const DEBUG_LABEL_FONT_SIZE: f32 = 16.0;
+// Labels are rendered at twice the size and scaled down, so they stay legible when zoomed in.
+const DEBUG_LABEL_SCALE: f32 = 0.5;
+const DEBUG_LABEL_Z: f32 = 60.0;
+
 #[derive(Component)]
 pub struct VisualDebug {
     ops: Vec<GizmoDrawOp>,
     persistent_op: Option<GizmoDrawOp>,
+    // Ops that hang around for a while after they're added, in real seconds.
+    timed_ops: Vec<(GizmoDrawOp, Timer)>,
 }
 impl Default for VisualDebug {
     fn default() -> Self {
         VisualDebug {
             ops: Vec::new(),
             persistent_op: None,
+            timed_ops: Vec::new(),
         }
     }
 }
@@ -94,6 +180,10 @@ impl VisualDebug {
     pub fn add(&mut self, op: GizmoDrawOp) {
         self.ops.push(op);
     }
+    pub fn add_timed(&mut self, op: GizmoDrawOp, secs: f32) {
+        self.timed_ops
+            .push((op, Timer::from_seconds(secs, TimerMode::Once)));
+    }
     pub fn register_persistent(&mut self, op: GizmoDrawOp) {
         self.persistent_op = Some(op);
     }
@@ -102,8 +192,8 @@ impl VisualDebug {
     }
     pub fn from_persistent(op: GizmoDrawOp) -> Self {
         VisualDebug {
-            ops: Vec::new(),
             persistent_op: Some(op),
+            ..default()
         }
     }
 }
@@ -112,6 +202,10 @@ pub enum GizmoDrawOp {
     Circle(CircleArgs),
     Line(LineArgs),
     Rect(RectArgs),
+    Arrow(LineArgs),
+    Polyline(PolylineArgs),
+    Arc(ArcArgs),
+    Text(TextArgs),
 }
 #[allow(dead_code)]
 impl GizmoDrawOp {
@@ -133,40 +227,142 @@ impl GizmoDrawOp {
             color,
         })
     }
+    pub fn arrow(start: Vec2, end: Vec2, color: Color) -> Self {
+        GizmoDrawOp::Arrow(LineArgs { start, end, color })
+    }
+    pub fn polyline(points: Vec<Vec2>, color: Color) -> Self {
+        GizmoDrawOp::Polyline(PolylineArgs { points, color })
+    }
+    // Angles in radians, with the arc centered on `direction_angle` - see Gizmos::arc_2d.
+    pub fn arc(
+        position: Vec2,
+        direction_angle: f32,
+        arc_angle: f32,
+        radius: f32,
+        color: Color,
+    ) -> Self {
+        GizmoDrawOp::Arc(ArcArgs {
+            position,
+            direction_angle,
+            arc_angle,
+            radius,
+            color,
+        })
+    }
+    pub fn text(position: Vec2, text: impl Into<String>, color: Color) -> Self {
+        GizmoDrawOp::Text(TextArgs {
+            position,
+            text: text.into(),
+            color,
+        })
+    }
+}
+
+#[derive(Component)]
+struct DebugLabel;
+
+// Gizmos can't draw text, so text ops are handed back to be drawn as world-space labels.
+fn draw_op<'a>(
+    gizmos: &mut Gizmos,
+    op: &'a GizmoDrawOp,
+    base: Vec2,
+    labels: &mut Vec<(Vec2, &'a TextArgs)>,
+) {
+    match op {
+        GizmoDrawOp::Circle(args) => {
+            gizmos.circle_2d(args.position + base, args.radius, args.color);
+        }
+        GizmoDrawOp::Line(args) => gizmos.line_2d(args.start + base, args.end + base, args.color),
+        GizmoDrawOp::Rect(args) => {
+            gizmos.rect_2d(args.position + base, args.rotation, args.size, args.color)
+        }
+        GizmoDrawOp::Arrow(args) => {
+            let (start, end) = (args.start + base, args.end + base);
+            gizmos.line_2d(start, end, args.color);
+            let back = (start - end).normalize_or_zero() * ARROW_HEAD_LENGTH;
+            for angle in [ARROW_HEAD_ANGLE, -ARROW_HEAD_ANGLE] {
+                gizmos.line_2d(end, end + Vec2::from_angle(angle).rotate(back), args.color);
+            }
+        }
+        GizmoDrawOp::Polyline(args) => {
+            gizmos.linestrip_2d(args.points.iter().map(|point| *point + base), args.color)
+        }
+        GizmoDrawOp::Arc(args) => {
+            gizmos.arc_2d(
+                args.position + base,
+                args.direction_angle,
+                args.arc_angle,
+                args.radius,
+                args.color,
+            );
+        }
+        GizmoDrawOp::Text(args) => labels.push((args.position + base, args)),
+    }
 }
 
-fn render_gizmos(mut gizmos: Gizmos, q: Query<(&VisualDebug, Option<&GlobalTransform>)>) {
+fn render_gizmos(
+    mut commands: Commands,
+    mut gizmos: Gizmos,
+    q: Query<(&VisualDebug, Option<&GlobalTransform>)>,
+    mut label_q: Query<(Entity, &mut Text, &mut Transform), With<DebugLabel>>,
+) {
+    let mut labels = Vec::new();
     for (dbg, has_transform) in q.iter() {
         if let Some(op) = &dbg.persistent_op {
             let base = match has_transform {
                 Some(transform) => transform.translation().xy(),
                 None => Vec2::ZERO,
             };
-            match op {
-                GizmoDrawOp::Circle(args) => {
-                    gizmos.circle_2d(args.position + base, args.radius, args.color);
-                }
-                GizmoDrawOp::Line(args) => gizmos.line_2d(args.start + base, args.end, args.color),
-                GizmoDrawOp::Rect(args) => {
-                    gizmos.rect_2d(args.position + base, args.rotation, args.size, args.color)
-                }
-            }
+            draw_op(&mut gizmos, op, base, &mut labels);
         }
-        for op in dbg.ops.iter() {
-            match &op {
-                GizmoDrawOp::Circle(args) => {
-                    gizmos.circle_2d(args.position, args.radius, args.color);
-                }
-                GizmoDrawOp::Line(args) => gizmos.line_2d(args.start, args.end, args.color),
-                GizmoDrawOp::Rect(args) => {
-                    gizmos.rect_2d(args.position, args.rotation, args.size, args.color)
-                }
-            }
+        for op in dbg.ops.iter().chain(dbg.timed_ops.iter().map(|(op, _)| op)) {
+            draw_op(&mut gizmos, op, Vec2::ZERO, &mut labels);
+        }
+    }
+
+    // Reuse the label entities from last frame where we can, rather than respawning every label every frame.
+    let mut pool = label_q.iter_mut();
+    for (position, args) in labels {
+        let translation = position.extend(DEBUG_LABEL_Z);
+        if let Some((_, mut text, mut transform)) = pool.next() {
+            text.sections[0].value.clone_from(&args.text);
+            text.sections[0].style.color = args.color;
+            transform.translation = translation;
+            continue;
         }
+        let mut transform = Transform::from_translation(translation);
+        transform.scale = Vec3::splat(DEBUG_LABEL_SCALE);
+        commands.spawn((
+            Text2dBundle {
+                text: Text::from_section(
+                    args.text.clone(),
+                    TextStyle {
+                        font_size: DEBUG_LABEL_FONT_SIZE,
+                        color: args.color,
+                        ..default()
+                    },
+                ),
+                transform,
+                ..default()
+            },
+            DebugLabel,
+        ));
+    }
+    for (entity, ..) in pool {
+        commands.entity(entity).despawn();
     }
 }
-fn clear_gizmos(mut q: Query<&mut VisualDebug>) {
+fn clear_gizmos(time: Res<Time>, mut q: Query<&mut VisualDebug>) {
     for mut dbg in q.iter_mut() {
         dbg.clear();
+        dbg.timed_ops.retain_mut(|(_, timer)| {
+            timer.tick(time.delta());
+            !timer.finished()
+        });
+    }
+}
+fn clear_debug_labels(mut commands: Commands, q: Query<Entity, With<DebugLabel>>) {
+    for entity in q.iter() {
+        commands.entity(entity).despawn();
     }
 }
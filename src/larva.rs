@@ -1,4 +1,4 @@
-use std::{cmp::Ordering, time::Duration};
+use std::time::Duration;
 
 use bevy::{
     ecs::system::{Command, SystemParam, SystemState},
@@ -7,31 +7,95 @@ use bevy::{
 
 use crate::{
     ant::{AntCommandsExt, NursemaidAnt},
-    colony::{AntCapacity, AntPopulation, Colony, LaborData, LaborPhase},
+    colony::{AntCapacity, AntPopulation, Colony, LaborData, LaborPhase, LarvaTarget},
     food::FoodQuant,
     gametimer::SimTimer,
+    queen::Queen,
+    stats::ColonyEvent,
 };
 
 pub struct LarvaPlugin;
 
 impl Plugin for LarvaPlugin {
     fn build(&self, app: &mut App) {
-        app.insert_resource(LarvaSettings::default()).add_systems(
-            Update,
-            (set_larva_pop.after(LaborPhase::Task), larva_eat).chain(),
-        );
+        app.insert_resource(LarvaSettings::default())
+            .init_resource::<BroodReport>()
+            .add_systems(
+                Update,
+                (set_larva_pop.after(LaborPhase::Task), larva_eat).chain(),
+            );
+    }
+}
+
+// Brood goes egg -> larva -> pupa -> ant. Only the larva stage eats, but every stage needs looking after.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum BroodStage {
+    Egg,
+    Larva,
+    Pupa,
+}
+impl BroodStage {
+    // How much of one nursemaid quota this stage takes up.
+    pub fn care(&self) -> f32 {
+        match self {
+            BroodStage::Egg => 0.5,
+            BroodStage::Larva => 1.0,
+            BroodStage::Pupa => 0.5,
+        }
+    }
+    fn next(&self) -> Option<BroodStage> {
+        match self {
+            BroodStage::Egg => Some(BroodStage::Larva),
+            BroodStage::Larva => Some(BroodStage::Pupa),
+            BroodStage::Pupa => None,
+        }
+    }
+    fn index(&self) -> f32 {
+        match self {
+            BroodStage::Egg => 0.0,
+            BroodStage::Larva => 1.0,
+            BroodStage::Pupa => 2.0,
+        }
     }
 }
 
 #[derive(Component)]
 pub struct Larva {
     colony: Entity,
+    stage: BroodStage,
+    // How far through the current stage we are, 0 to 1.
     growth: f32,
+    // Set whenever the colony couldn't pay for this larva's last meal.
+    starving: bool,
+}
+impl Larva {
+    pub fn stage(&self) -> BroodStage {
+        self.stage
+    }
+    // How far from egg to ant, 0 to 1, across all the stages.
+    pub fn progress(&self) -> f32 {
+        (self.stage.index() + self.growth) / 3.0
+    }
+    pub fn is_starving(&self) -> bool {
+        self.starving
+    }
 }
 
 #[derive(Component)]
 pub struct GrowthTimer;
 
+// Brood the colony can't look after right now. They neither eat nor grow, but they keep what they've been fed so far.
+#[derive(Component)]
+pub struct PausedBrood;
+
+// What set_larva_pop couldn't do this frame, for the UI.
+#[derive(Resource, Default)]
+pub struct BroodReport {
+    pub paused: i32,
+    // Eggs we have room for but the queen hasn't laid yet.
+    pub awaiting_eggs: i32,
+}
+
 struct NewLarva;
 impl Command for NewLarva {
     fn apply(self, world: &mut World) {
@@ -41,7 +105,9 @@ impl Command for NewLarva {
         commands
             .spawn(Larva {
                 growth: 0.0,
+                stage: BroodStage::Egg,
                 colony: col_q.single(),
+                starving: false,
             })
             .with_children(|c_commands| {
                 c_commands.spawn((
@@ -61,23 +127,39 @@ impl Command for NewLarva {
 #[derive(Resource)]
 pub struct LarvaSettings {
     pub nursemaids_per_larva: f32,
-    food_per_tick: i32,
-    ticks_til_grown: i32,
+    // Food to raise one ant, all of it eaten during the larva stage.
+    food_per_ant: i32,
+    egg_ticks: i32,
+    larva_ticks: i32,
+    pupa_ticks: i32,
     ticks_per_sec: f32,
 }
 impl Default for LarvaSettings {
     fn default() -> Self {
         LarvaSettings {
             nursemaids_per_larva: 5.0,
-            food_per_tick: 1,
-            ticks_til_grown: 20,
+            food_per_ant: 20,
+            egg_ticks: 5,
+            larva_ticks: 10,
+            pupa_ticks: 5,
             ticks_per_sec: 0.25,
         }
     }
 }
 impl LarvaSettings {
-    fn growth_per_tick(&self) -> f32 {
-        (self.ticks_til_grown as f32).recip()
+    fn growth_per_tick(&self, stage: BroodStage) -> f32 {
+        let ticks = match stage {
+            BroodStage::Egg => self.egg_ticks,
+            BroodStage::Larva => self.larva_ticks,
+            BroodStage::Pupa => self.pupa_ticks,
+        };
+        (ticks as f32).recip()
+    }
+    fn food_per_tick(&self, stage: BroodStage) -> i32 {
+        match stage {
+            BroodStage::Larva => self.food_per_ant / self.larva_ticks,
+            _ => 0,
+        }
     }
     fn secs_per_tick(&self) -> f32 {
         self.ticks_per_sec.recip()
@@ -85,12 +167,12 @@ impl LarvaSettings {
 }
 
 #[derive(SystemParam)]
-struct NurseableLarva<'w, 's> {
+pub struct NurseableLarva<'w, 's> {
     larva_settings: Res<'w, LarvaSettings>,
     labor_query: Query<'w, 's, &'static LaborData<NursemaidAnt>>,
 }
 impl<'w, 's> NurseableLarva<'w, 's> {
-    fn get(&self) -> i32 {
+    pub fn get(&self) -> i32 {
         let mut res = 0;
         self.labor_query.iter().for_each(|n| {
             res += (n.active as f32 / self.larva_settings.nursemaids_per_larva).trunc() as i32;
@@ -101,21 +183,33 @@ impl<'w, 's> NurseableLarva<'w, 's> {
 
 fn larva_eat(
     mut commands: Commands,
+    mut colony_events: EventWriter<ColonyEvent>,
     l_settings: Res<LarvaSettings>,
-    mut p_q: Query<&mut Larva, With<Children>>,
+    mut p_q: Query<&mut Larva, (With<Children>, Without<PausedBrood>)>,
     t_q: Query<(&SimTimer, &Parent), With<GrowthTimer>>,
     mut col_q: Query<(&mut FoodQuant, &AntCapacity, &AntPopulation), With<Colony>>,
 ) {
     let (mut food, ant_cap, ant_pop) = col_q.single_mut();
     t_q.iter().for_each(|(timer, parent_entity)| {
         if timer.time.finished() {
-            if let Ok(mut larva) = p_q.get_mut(parent_entity.get()) {
-                if food.0 > l_settings.food_per_tick && ant_pop.0 < ant_cap.0 {
-                    food.0 -= l_settings.food_per_tick;
-                    larva.growth += l_settings.growth_per_tick();
+            let brood_entity = parent_entity.get();
+            if let Ok(mut larva) = p_q.get_mut(brood_entity) {
+                let meal = l_settings.food_per_tick(larva.stage);
+                larva.starving = meal > 0 && food.0 <= meal;
+                if !larva.starving && ant_pop.0 < ant_cap.0 {
+                    food.0 -= meal;
+                    larva.growth += l_settings.growth_per_tick(larva.stage);
                     if larva.growth >= 1.0 {
                         larva.growth = (larva.growth - 1.0).trunc();
-                        commands.spawn_ant(larva.colony, Vec2::ZERO)
+                        match larva.stage.next() {
+                            Some(stage) => larva.stage = stage,
+                            // The pupa is used up - set_larva_pop only replaces it with a fresh egg from the queen.
+                            None => {
+                                commands.spawn_ant(larva.colony, Vec2::ZERO);
+                                commands.entity(brood_entity).despawn_recursive();
+                                colony_events.send(ColonyEvent::AntBorn);
+                            }
+                        }
                     }
                 }
             }
@@ -123,34 +217,168 @@ fn larva_eat(
     })
 }
 
+struct BroodPlan {
+    // How many brood, most grown first, the nursemaids can keep going.
+    keep: usize,
+    new_eggs: i32,
+}
+
+// Brood are kept by head count up to the target, and by how much care each stage needs up to what the nursemaids can give.
+// Anything that doesn't fit is paused, and new eggs only go in once all the paused brood are back on.
+fn plan_brood(target: i32, care_budget: f32, brood: &[BroodStage]) -> BroodPlan {
+    let mut care_left = care_budget;
+    let mut keep = 0;
+    for stage in brood {
+        if keep as i32 >= target || stage.care() > care_left {
+            break;
+        }
+        care_left -= stage.care();
+        keep += 1;
+    }
+    let new_eggs = if keep < brood.len() {
+        0
+    } else {
+        (target - keep as i32)
+            .min((care_left / BroodStage::Egg.care()).floor() as i32)
+            .max(0)
+    };
+    BroodPlan { keep, new_eggs }
+}
+
 fn set_larva_pop(
     mut commands: Commands,
-    current_larva_cap: NurseableLarva,
-    larva_q: Query<(Entity, &Larva)>,
+    nurseable: NurseableLarva,
+    mut report: ResMut<BroodReport>,
+    col_q: Query<&LarvaTarget, With<Colony>>,
+    larva_q: Query<(Entity, &Larva, Has<PausedBrood>)>,
+    mut queen_q: Query<&mut Queen>,
 ) {
-    let larva_cap = current_larva_cap.get();
-    let larva_pop = larva_q.iter().len() as i32;
-    let mut delta = larva_cap - larva_pop;
-    match delta.signum() {
-        1 => {
-            while delta > 0 {
-                commands.add(NewLarva);
-                delta = -1;
+    let Ok(target) = col_q.get_single() else {
+        return;
+    };
+    let mut brood = larva_q.iter().collect::<Vec<(Entity, &Larva, bool)>>();
+    // Ties go to brood that are already being looked after, so we don't shuffle which ones are paused every frame.
+    brood.sort_by(|(_, a, a_paused), (_, b, b_paused)| {
+        b.progress()
+            .total_cmp(&a.progress())
+            .then(a_paused.cmp(b_paused))
+    });
+    let stages = brood
+        .iter()
+        .map(|(_, larva, _)| larva.stage())
+        .collect::<Vec<BroodStage>>();
+    let plan = plan_brood(target.0, nurseable.get() as f32, &stages);
+
+    for (i, (entity, _, paused)) in brood.iter().enumerate() {
+        match (i < plan.keep, *paused) {
+            (true, true) => {
+                commands.entity(*entity).remove::<PausedBrood>();
             }
+            (false, false) => {
+                commands.entity(*entity).insert(PausedBrood);
+            }
+            _ => {}
         }
-        -1 => {
-            let mut l_vec = larva_q.iter().collect::<Vec<(Entity, &Larva)>>();
-            l_vec.sort_by(|(_, a), (_, b)| {
-                a.growth.partial_cmp(&b.growth).unwrap_or(Ordering::Equal)
-            });
+    }
 
-            l_vec
-                .iter()
-                .take(delta.abs() as usize)
-                .for_each(|(entity, _)| {
-                    commands.entity(*entity).despawn_recursive();
-                });
+    // Every new larva starts as one of the queen's eggs, so she sets the pace.
+    let mut laid = 0;
+    if let Ok(mut queen) = queen_q.get_single_mut() {
+        while laid < plan.new_eggs && queen.lay_egg() {
+            commands.add(NewLarva);
+            laid += 1;
         }
-        _ => {}
+    }
+    report.paused = (brood.len() - plan.keep) as i32;
+    report.awaiting_eggs = plan.new_eggs - laid;
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::queen::QueenSettings;
+
+    fn brood_app(target: i32) -> App {
+        let mut app = App::new();
+        app.insert_resource(LarvaSettings::default())
+            .init_resource::<BroodReport>()
+            .add_systems(Update, set_larva_pop);
+        let colony = app
+            .world
+            .spawn((
+                Colony,
+                LarvaTarget(target),
+                LaborData::<NursemaidAnt>::default(),
+            ))
+            .id();
+        app.world
+            .get_mut::<LaborData<NursemaidAnt>>(colony)
+            .unwrap()
+            .active = 50;
+        let queen_settings = QueenSettings {
+            clutch_size: 10.0,
+            ..default()
+        };
+        app.world.spawn(Queen::new(colony, &queen_settings));
+        app
+    }
+
+    fn set_target(app: &mut App, target: i32) {
+        let mut q = app.world.query_filtered::<&mut LarvaTarget, With<Colony>>();
+        q.single_mut(&mut app.world).0 = target;
+    }
+
+    fn count_brood(app: &mut App) -> (usize, usize) {
+        let total = app.world.query::<&Larva>().iter(&app.world).count();
+        let paused = app
+            .world
+            .query_filtered::<(), With<PausedBrood>>()
+            .iter(&app.world)
+            .count();
+        (total, paused)
+    }
+
+    #[test]
+    fn raising_target_spawns_whole_deficit() {
+        let mut app = brood_app(1);
+        app.update();
+        assert_eq!(count_brood(&mut app), (1, 0));
+
+        set_target(&mut app, 4);
+        app.update();
+        assert_eq!(count_brood(&mut app), (4, 0));
+        assert_eq!(app.world.resource::<BroodReport>().awaiting_eggs, 0);
+    }
+
+    #[test]
+    fn raising_target_waits_on_the_queen() {
+        let mut app = brood_app(1);
+        app.world
+            .resource_mut::<LarvaSettings>()
+            .nursemaids_per_larva = 1.0;
+        app.update();
+        set_target(&mut app, 20);
+        app.update();
+        // The queen only had ten eggs to give.
+        assert_eq!(count_brood(&mut app), (10, 0));
+        assert_eq!(app.world.resource::<BroodReport>().awaiting_eggs, 10);
+    }
+
+    #[test]
+    fn lowering_target_pauses_surplus() {
+        let mut app = brood_app(4);
+        app.update();
+        assert_eq!(count_brood(&mut app), (4, 0));
+
+        set_target(&mut app, 1);
+        app.update();
+        app.update();
+        assert_eq!(count_brood(&mut app), (4, 3));
+        assert_eq!(app.world.resource::<BroodReport>().paused, 3);
+
+        set_target(&mut app, 4);
+        app.update();
+        app.update();
+        assert_eq!(count_brood(&mut app), (4, 0));
     }
 }
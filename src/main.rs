@@ -1,6 +1,7 @@
 #![cfg_attr(not(debug_assertions), windows_subsystem = "windows")]
 mod ant;
 mod app_settings;
+mod calendar;
 mod colony;
 mod food;
 mod gametimer;
@@ -9,12 +10,18 @@ mod larva;
 mod misc_utility;
 mod nav;
 mod playerinput;
+mod queen;
+mod stats;
+mod terrain;
+mod transport;
 mod ui;
+mod weather;
+mod worldgen;
 
 use std::time::Duration;
 
 use ant::AntPlugin;
-use app_settings::{AppSettingsPlugin, DisplaySettings, SoundType, VolumeSettings};
+use app_settings::{AppSettingsPlugin, CameraSettings, DisplaySettings, SoundType, VolumeSettings};
 use bevy::asset::{load_internal_binary_asset, AssetMetaCheck};
 use bevy::audio::VolumeLevel;
 use bevy::window::WindowMode;
@@ -27,6 +34,7 @@ use bevy_nine_slice_ui::*;
 use bevy_prng::WyRand;
 use bevy_rand::prelude::*;
 use bevy_spatial::{AutomaticUpdate, SpatialStructure};
+use calendar::CalendarPlugin;
 use colony::ColonyPlugin;
 use food::FoodPlugin;
 use gametimer::GameTimerPlugin;
@@ -34,7 +42,16 @@ use gizmodable::Gizmotastic;
 use larva::LarvaPlugin;
 use nav::ScentMapPlugin;
 use playerinput::PlayerInputPlugin;
-use ui::{CreditsPlugin, GamefieldUI, MainMenuUI, SettingsMenuPlugin, UpgradePlugin};
+use queen::QueenPlugin;
+use stats::StatsPlugin;
+use terrain::TerrainPlugin;
+use transport::TransportPlugin;
+use ui::{
+    AntInspectorPlugin, BroodChamberPlugin, CreditsPlugin, DebugMenuPlugin, FocusNavigationPlugin,
+    GamefieldUI, MainMenuUI, MinimapPlugin, SettingsMenuPlugin, StatsPanelPlugin, UpgradePlugin,
+};
+use weather::WeatherPlugin;
+use worldgen::WorldGenPlugin;
 
 fn main() {
     let mut app = App::new();
@@ -60,11 +77,13 @@ fn main() {
         .add_plugins(EntropyPlugin::<WyRand>::default())
         .register_type::<VolumeSettings>()
         .register_type::<DisplaySettings>()
+        .register_type::<CameraSettings>()
         .add_state::<UIFocus>()
         .add_state::<SimState>()
         .add_event::<SoundScape>()
         .init_resource::<VolumeSettings>()
         .init_resource::<DisplaySettings>()
+        .init_resource::<CameraSettings>()
         .add_plugins(
             AutomaticUpdate::<SpatialMarker>::new()
                 .with_spatial_ds(SpatialStructure::KDTree2)
@@ -73,23 +92,43 @@ fn main() {
         .add_plugins(
             AutomaticUpdate::<AntSpatialMarker>::new().with_spatial_ds(SpatialStructure::KDTree2),
         )
+        .add_plugins(
+            AutomaticUpdate::<ObstacleSpatialMarker>::new()
+                .with_spatial_ds(SpatialStructure::KDTree2)
+                .with_frequency(Duration::from_secs_f32(2.0)),
+        )
         .add_plugins((
             AppSettingsPlugin,
             MainMenuUI,
             SettingsMenuPlugin,
             CreditsPlugin,
             GameTimerPlugin,
+            CalendarPlugin,
+            WeatherPlugin,
             PlayerInputPlugin,
+            StatsPlugin,
         ))
         .add_plugins((
             Gizmotastic,
             ColonyPlugin,
             LarvaPlugin,
+            QueenPlugin,
             AntPlugin,
             UpgradePlugin,
             ScentMapPlugin,
             FoodPlugin,
+            TerrainPlugin,
+            TransportPlugin,
+            WorldGenPlugin,
+        ))
+        .add_plugins((
             GamefieldUI,
+            BroodChamberPlugin,
+            StatsPanelPlugin,
+            AntInspectorPlugin,
+            DebugMenuPlugin,
+            FocusNavigationPlugin,
+            MinimapPlugin,
         ))
         .configure_sets(
             Startup,
@@ -138,6 +177,9 @@ pub struct SpatialMarker;
 #[derive(Component, Default)]
 pub struct AntSpatialMarker;
 
+#[derive(Component, Default)]
+pub struct ObstacleSpatialMarker;
+
 #[derive(SystemSet, Hash, Debug, PartialEq, Eq, Clone)]
 pub enum InitializationPhase {
     LoadConfigurations,
@@ -151,6 +193,8 @@ pub enum SimState {
     Paused,
     MenuOpenedWhilePaused,
     Playing,
+    // The queen is dead - nothing gets the sim going again.
+    GameOver,
 }
 #[derive(Debug, Clone, Copy, Default, Eq, PartialEq, Hash, States)]
 pub enum UIFocus {
@@ -168,6 +212,7 @@ pub enum SoundScape {
     FoodSpawn,
     FoodEmpty,
     AntBorn,
+    WeatherChange,
 }
 #[derive(Component)]
 pub struct MainCamera;
@@ -206,6 +251,9 @@ fn boot_camera(
 
 
 fn start_game(mut sim_state: ResMut<NextState<SimState>>, current_sim_state: Res<State<SimState>>) {
+    if matches!(current_sim_state.get(), SimState::GameOver) {
+        return;
+    }
     if matches!(current_sim_state.get(), SimState::MenuOpenedWhilePaused) {
         sim_state.set(SimState::Paused);
         return;
@@ -213,6 +261,9 @@ fn start_game(mut sim_state: ResMut<NextState<SimState>>, current_sim_state: Res
     sim_state.set(SimState::Playing);
 }
 fn pause_game(mut sim_state: ResMut<NextState<SimState>>, current_sim_state: Res<State<SimState>>) {
+    if matches!(current_sim_state.get(), SimState::GameOver) {
+        return;
+    }
     if matches!(current_sim_state.get(), SimState::Paused) {
         sim_state.set(SimState::MenuOpenedWhilePaused);
         return;
@@ -247,6 +298,7 @@ fn soundscape_processor(
             SoundScape::AntDeath => "Click.wav",
             SoundScape::FoodEmpty => "D_vib.wav",
             SoundScape::FoodSpawn => "G_vib.wav",
+            SoundScape::WeatherChange => "Fs_vib.wav",
         };
         commands.spawn((
             AudioBundle {
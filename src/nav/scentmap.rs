@@ -1,14 +1,15 @@
 use bevy::{math::Vec3Swizzles, prelude::*, time::common_conditions::on_timer, utils::HashMap};
 use kd_tree::KdTree;
-use std::time::Duration;
+use std::{f32::consts::FRAC_PI_4, time::Duration};
 
-use crate::SimState;
+use crate::{weather::Weather, SimState};
 
 pub struct ScentMapPlugin;
 
 impl Plugin for ScentMapPlugin {
     fn build(&self, app: &mut App) {
-        app.insert_resource(ScentSettings::default())
+        app.register_type::<ScentSettings>()
+            .insert_resource(ScentSettings::default())
             .insert_resource(ScentMap::new())
             .add_systems(
                 Update,
@@ -19,8 +20,8 @@ impl Plugin for ScentMapPlugin {
     }
 }
 
-fn update_index(mut map: ResMut<ScentMap>, settings: Res<ScentSettings>) {
-    map.decay_smells(settings.decay_rate);
+fn update_index(mut map: ResMut<ScentMap>, settings: Res<ScentSettings>, weather: Res<Weather>) {
+    map.decay_smells(settings.decay_rate * weather.scent_decay_factor());
     map.cull_zeros_and_nans();
     map.update_trees();
 }
@@ -160,6 +161,10 @@ impl ScentMap {
         if dump.len() == 0 {
             return None;
         }
+        // Cells that have decayed to zero but haven't been culled yet would leave us dividing by zero below.
+        if dump.iter().all(|(_, _, weight)| *weight <= 0.0) {
+            return None;
+        }
 
         let mut total_weight = 0.0;
         let mut weighted_sum_x = 0.0;
@@ -176,7 +181,7 @@ impl ScentMap {
         let weighted_midpoint_x = weighted_sum_x * total_weight_recip;
         let weighted_midpoint_y = weighted_sum_y * total_weight_recip;
         if weighted_midpoint_x.is_nan() || weighted_midpoint_y.is_nan() {
-            info!("FUCKING NAN")
+            return None;
         }
         let vec = Vec2 {
             x: weighted_midpoint_x,
@@ -184,14 +189,93 @@ impl ScentMap {
         };
         Some(vec)
     }
+
+    // Classic ant-colony sensing - sample three points ahead of our heading and steer toward whichever smells strongest.
+    pub fn strongest_smell_antennae(
+        &self,
+        settings: &ScentSettings,
+        scent: ScentType,
+        weighting: WeightType,
+        transform: &GlobalTransform,
+    ) -> Option<Vec2> {
+        let span = info_span!("scentmap: get smell antennae");
+        let _ = span.enter();
+        let tree = match scent {
+            ScentType::FoundFoodSmell => &self.found_food_smell_index,
+            ScentType::AntSmell => &self.ant_smell_index,
+        };
+        let hashmap = match scent {
+            ScentType::FoundFoodSmell => &self.found_food_smell_data,
+            ScentType::AntSmell => &self.ant_smell_data,
+        };
+        let current_pos = transform.translation().xy();
+        let heading = transform.up().xy().normalize_or_zero();
+        if heading == Vec2::ZERO {
+            return None;
+        }
+
+        let mut strongest: Option<(Vec2, f32)> = None;
+        for angle in [settings.antenna_angle, 0.0, -settings.antenna_angle] {
+            let sensor =
+                current_pos + Vec2::from_angle(angle).rotate(heading) * settings.antenna_reach;
+            let permitted = match weighting {
+                WeightType::CloserTo(home) => sensor.distance(home) < current_pos.distance(home),
+                WeightType::FurtherFrom(home) => sensor.distance(home) > current_pos.distance(home),
+                _ => true,
+            };
+            if !permitted {
+                continue;
+            }
+            let strength: f32 = tree
+                .within_radius(&[sensor.x, sensor.y], settings.antenna_radius)
+                .iter()
+                .filter_map(|[x, y]| hashmap.get(&(*x as i32, *y as i32)))
+                .sum();
+            if strength <= 0.0 || strength.is_nan() {
+                continue;
+            }
+            if !strongest.is_some_and(|(_, best)| best >= strength) {
+                strongest = Some((sensor, strength));
+            }
+        }
+        strongest.map(|(sensor, _)| sensor)
+    }
+
+    pub fn strongest_smell(
+        &mut self,
+        settings: &ScentSettings,
+        scent: ScentType,
+        weighting: WeightType,
+        transform: &GlobalTransform,
+    ) -> Option<Vec2> {
+        match settings.sampling {
+            SmellSampling::WeightedCentroid => {
+                self.strongest_smell_weighted(settings.smell_radius, scent, weighting, transform)
+            }
+            SmellSampling::Antennae => {
+                self.strongest_smell_antennae(settings, scent, weighting, transform)
+            }
+        }
+    }
 }
 
-#[derive(Resource)]
+#[derive(Reflect, Clone, Copy, Debug, PartialEq, Eq)]
+pub enum SmellSampling {
+    WeightedCentroid,
+    Antennae,
+}
+
+#[derive(Resource, Reflect)]
+#[reflect(Resource)]
 pub struct ScentSettings {
     pub decay_rate: f32,
     pub smell_radius: f32,
     pub starting_strength: f32,
     pub max_smell: f32,
+    pub sampling: SmellSampling,
+    pub antenna_reach: f32,
+    pub antenna_angle: f32,
+    pub antenna_radius: f32,
 }
 impl Default for ScentSettings {
     fn default() -> Self {
@@ -200,6 +284,10 @@ impl Default for ScentSettings {
             smell_radius: 10.0,
             starting_strength: 50.0,
             max_smell: 150.0,
+            sampling: SmellSampling::WeightedCentroid,
+            antenna_reach: 10.0,
+            antenna_angle: FRAC_PI_4,
+            antenna_radius: 4.0,
         }
     }
 }
@@ -1,34 +1,84 @@
-use bevy::{prelude::*, window::PrimaryWindow};
-use leafwing_input_manager::{prelude::*, user_input::InputKind};
+use bevy::{
+    ecs::system::SystemParam, input::touch::Touch, prelude::*, utils::HashSet, window::PrimaryWindow,
+};
+use bevy_spatial::{kdtree::KDTree2, SpatialAccess};
+use leafwing_input_manager::{
+    axislike::VirtualAxis, plugin::InputManagerSystem, prelude::*, user_input::InputKind,
+};
+use serde::{Deserialize, Serialize};
 
 use crate::{
     ui::{
         credits_ui::CreditsUIActions,
         menu_ui::MainMenuUIActions,
+        inspector_ui::{InspectedAnt, PICK_RADIUS},
         settings_menu::{AudioMenuUIActions, DisplaySettingsMenuUIActions, SettingsMenuUIActions},
+        FocusedButton,
     },
-    MainCamera, SimState, UIFocus,
+    ant::Ant,
+    app_settings::CameraSettings,
+    colony::{Colony, ColonyPos},
+    worldgen::WorldBounds,
+    AntSpatialMarker, MainCamera, SimState, UIFocus,
 };
 
 const CAMERA_PAN_SPEED_FACTOR: f32 = 10.0;
+const CAMERA_MIN_SCALE: f32 = 0.1;
+const CAMERA_MAX_SCALE: f32 = 20.0;
+// Matches the AutoMin scaling mode the main camera boots with - at a scale of 1.0 we see at least this much of the world.
+const CAMERA_MIN_VIEW: Vec2 = Vec2::new(800.0, 450.0);
 #[cfg(not(target_arch = "wasm32"))]
 const CAMERA_ZOOM_RATE: f32 = 0.05;
 #[cfg(target_arch = "wasm32")]
 const CAMERA_ZOOM_RATE: f32 = 0.005;
+// How close to the edge of the window, in logical pixels, the cursor has to be to start edge scrolling.
+const CAMERA_EDGE_SCROLL_MARGIN: f32 = 12.0;
+// How quickly a gliding camera slows down, and the speed (world units per second) it stops at.
+const CAMERA_GLIDE_DRAG: f32 = 5.0;
+const CAMERA_GLIDE_STOP: f32 = 5.0;
+// How much of each frame's panning goes into the velocity the camera glides on with.
+const CAMERA_GLIDE_SMOOTHING: f32 = 0.5;
+// How long a finger has to stay put to count as a long press, and how far (logical pixels) it can wander meanwhile.
+const LONG_PRESS_SECS: f32 = 0.5;
+const LONG_PRESS_SLOP: f32 = 8.0;
 pub struct PlayerInputPlugin;
 
 impl Plugin for PlayerInputPlugin {
     fn build(&self, app: &mut App) {
         app.add_plugins(InputManagerPlugin::<CameraControl>::default())
             .add_plugins(InputManagerPlugin::<GamefieldActions>::default())
+            .add_event::<LongPress>()
+            .init_resource::<UiTouches>()
             .add_systems(Startup, setup)
             .add_systems(
                 OnEnter(UIFocus::Gamefield),
                 game_field_setup.run_if(run_once()),
             )
+            .add_systems(Update, apply_control_bindings.run_if(controls_changed))
+            .add_systems(
+                PreUpdate,
+                hold_click_for_focused_button.after(InputManagerSystem::Update),
+            )
             .add_systems(
                 Update,
-                (pan_camera, zoom_camera, user_toggle_pause, player_open_menu)
+                (
+                    (
+                        pan_camera,
+                        edge_scroll_camera.run_if(edge_scroll_enabled),
+                        drag_pan_camera.run_if(drag_pan_enabled),
+                        track_ui_touches,
+                        touch_camera,
+                        touch_long_press,
+                        glide_camera,
+                        zoom_camera,
+                        recenter_on_nest,
+                        follow_inspected_ant,
+                        clamp_camera_to_world,
+                    )
+                        .chain(),
+                    user_toggle_pause,
+                    player_open_menu,
+                )
                     .run_if(in_state(UIFocus::Gamefield)),
             );
     }
@@ -43,12 +93,342 @@ pub enum CameraControl {
     Zoom,
 }
 
-#[derive(Actionlike, Clone, Debug, Copy, PartialEq, Eq, Hash, Reflect)]
+#[derive(Actionlike, Clone, Debug, Copy, PartialEq, Eq, Hash, Reflect, Serialize, Deserialize)]
 pub enum GamefieldActions {
     TogglePause,
     GameFieldClick,
     OpenMainMenu,
+    RecenterOnNest,
+    BuildEntrance,
+    ToggleStats,
+    FollowAnt,
+    ToggleDebugMenu,
+    BuyAntCapacity,
+    BuyMaxFood,
+    BuyCarryCapacity,
+    BuyQueenFertility,
+    LarvaUp,
+    LarvaDown,
+}
+
+// Everything the player can rebind.
+// The camera's pan and zoom are axes as far as LWIM is concerned, so they're split up into the buttons that drive them.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash, Serialize, Deserialize)]
+pub enum Control {
+    Gamefield(GamefieldActions),
+    DragPan,
+    PanUp,
+    PanDown,
+    PanLeft,
+    PanRight,
+    ZoomIn,
+    ZoomOut,
+}
+impl Control {
+    // In the order the controls menu lists them.
+    pub fn all() -> Vec<Control> {
+        GamefieldActions::variants()
+            .map(Control::Gamefield)
+            .chain([
+                Control::DragPan,
+                Control::PanUp,
+                Control::PanDown,
+                Control::PanLeft,
+                Control::PanRight,
+                Control::ZoomIn,
+                Control::ZoomOut,
+            ])
+            .collect()
+    }
+    pub fn name(&self) -> &'static str {
+        match self {
+            Control::Gamefield(action) => match action {
+                GamefieldActions::TogglePause => "Pause",
+                GamefieldActions::GameFieldClick => "Select",
+                GamefieldActions::OpenMainMenu => "Main menu",
+                GamefieldActions::RecenterOnNest => "Go to nest",
+                GamefieldActions::BuildEntrance => "Build entrance",
+                GamefieldActions::ToggleStats => "Stats",
+                GamefieldActions::FollowAnt => "Follow ant",
+                GamefieldActions::ToggleDebugMenu => "Debug menu",
+                GamefieldActions::BuyAntCapacity => "Buy ant capacity",
+                GamefieldActions::BuyMaxFood => "Buy max food",
+                GamefieldActions::BuyCarryCapacity => "Buy carry capacity",
+                GamefieldActions::BuyQueenFertility => "Buy fertility",
+                GamefieldActions::LarvaUp => "More larva",
+                GamefieldActions::LarvaDown => "Fewer larva",
+            },
+            Control::DragPan => "Drag camera",
+            Control::PanUp => "Pan up",
+            Control::PanDown => "Pan down",
+            Control::PanLeft => "Pan left",
+            Control::PanRight => "Pan right",
+            Control::ZoomIn => "Zoom in",
+            Control::ZoomOut => "Zoom out",
+        }
+    }
+}
+
+// The gamepad isn't rebindable - these sit alongside whatever the keyboard and mouse are bound to.
+// The D-pad is left free for moving focus around the UI, and the sticks and triggers drive the camera.
+const GAMEPAD_BINDINGS: [(GamepadButtonType, GamefieldActions); 8] = [
+    (GamepadButtonType::Select, GamefieldActions::TogglePause),
+    (GamepadButtonType::South, GamefieldActions::GameFieldClick),
+    (GamepadButtonType::Start, GamefieldActions::OpenMainMenu),
+    (GamepadButtonType::West, GamefieldActions::RecenterOnNest),
+    (GamepadButtonType::North, GamefieldActions::BuildEntrance),
+    (GamepadButtonType::LeftTrigger, GamefieldActions::ToggleStats),
+    (GamepadButtonType::RightTrigger, GamefieldActions::FollowAnt),
+    (GamepadButtonType::RightThumb, GamefieldActions::ToggleDebugMenu),
+];
+
+// What each control is bound to. Persisted as part of the user settings.
+#[derive(Resource, Clone, Serialize, Deserialize)]
+pub struct ControlBindings(Vec<(Control, InputKind)>);
+impl Default for ControlBindings {
+    fn default() -> Self {
+        ControlBindings(vec![
+            (
+                Control::Gamefield(GamefieldActions::TogglePause),
+                InputKind::Keyboard(KeyCode::Space),
+            ),
+            (
+                Control::Gamefield(GamefieldActions::GameFieldClick),
+                InputKind::Mouse(MouseButton::Left),
+            ),
+            (
+                Control::Gamefield(GamefieldActions::OpenMainMenu),
+                InputKind::Keyboard(KeyCode::Escape),
+            ),
+            (
+                Control::Gamefield(GamefieldActions::RecenterOnNest),
+                InputKind::Keyboard(KeyCode::H),
+            ),
+            (
+                Control::Gamefield(GamefieldActions::BuildEntrance),
+                InputKind::Keyboard(KeyCode::B),
+            ),
+            (
+                Control::Gamefield(GamefieldActions::ToggleStats),
+                InputKind::Keyboard(KeyCode::Tab),
+            ),
+            (
+                Control::Gamefield(GamefieldActions::FollowAnt),
+                InputKind::Keyboard(KeyCode::F),
+            ),
+            (
+                Control::Gamefield(GamefieldActions::ToggleDebugMenu),
+                InputKind::Keyboard(KeyCode::F3),
+            ),
+            (
+                Control::Gamefield(GamefieldActions::BuyAntCapacity),
+                InputKind::Keyboard(KeyCode::Key1),
+            ),
+            (
+                Control::Gamefield(GamefieldActions::BuyMaxFood),
+                InputKind::Keyboard(KeyCode::Key2),
+            ),
+            (
+                Control::Gamefield(GamefieldActions::BuyCarryCapacity),
+                InputKind::Keyboard(KeyCode::Key3),
+            ),
+            (
+                Control::Gamefield(GamefieldActions::BuyQueenFertility),
+                InputKind::Keyboard(KeyCode::Key4),
+            ),
+            (
+                Control::Gamefield(GamefieldActions::LarvaUp),
+                InputKind::Keyboard(KeyCode::Equals),
+            ),
+            (
+                Control::Gamefield(GamefieldActions::LarvaDown),
+                InputKind::Keyboard(KeyCode::Minus),
+            ),
+            (Control::DragPan, InputKind::Mouse(MouseButton::Middle)),
+            (Control::PanUp, InputKind::Keyboard(KeyCode::W)),
+            (Control::PanDown, InputKind::Keyboard(KeyCode::S)),
+            (Control::PanLeft, InputKind::Keyboard(KeyCode::A)),
+            (Control::PanRight, InputKind::Keyboard(KeyCode::D)),
+            (
+                Control::ZoomIn,
+                InputKind::MouseWheel(MouseWheelDirection::Up),
+            ),
+            (
+                Control::ZoomOut,
+                InputKind::MouseWheel(MouseWheelDirection::Down),
+            ),
+        ])
+    }
+}
+impl ControlBindings {
+    // Settings saved before a control existed won't have it, so those fall back to the default binding.
+    pub fn get(&self, control: Control) -> InputKind {
+        self.0
+            .iter()
+            .chain(ControlBindings::default().0.iter())
+            .find(|(bound, _)| *bound == control)
+            .map(|(_, input)| *input)
+            .expect("every control has a default binding")
+    }
+    // Binds the control to the input. Whatever was already on that input gets the control's old binding instead, so
+    // nothing is ever left unbound. Returns the control that got swapped, if any.
+    pub fn rebind(&mut self, control: Control, input: InputKind) -> Option<Control> {
+        let previous = self.get(control);
+        let swapped = Control::all()
+            .into_iter()
+            .find(|other| *other != control && self.get(*other) == input);
+        self.set(control, input);
+        if let Some(other) = swapped {
+            self.set(other, previous);
+        }
+        swapped
+    }
+    // Controls that share their input with some other control - only possible from a hand edited settings file.
+    pub fn conflicts(&self) -> Vec<Control> {
+        let all = Control::all();
+        all.iter()
+            .copied()
+            .filter(|control| {
+                all.iter()
+                    .any(|other| other != control && self.get(*other) == self.get(*control))
+            })
+            .collect()
+    }
+    fn set(&mut self, control: Control, input: InputKind) {
+        self.0.retain(|(bound, _)| *bound != control);
+        self.0.push((control, input));
+    }
+    fn gamefield_map(&self) -> InputMap<GamefieldActions> {
+        let mut map = InputMap::new(
+            GamefieldActions::variants()
+                .map(|action| (self.get(Control::Gamefield(action)), action)),
+        );
+        map.insert_multiple(GAMEPAD_BINDINGS);
+        map
+    }
+    fn camera_map(&self) -> InputMap<CameraControl> {
+        InputMap::default()
+            .insert(
+                VirtualAxis {
+                    negative: self.get(Control::ZoomOut),
+                    positive: self.get(Control::ZoomIn),
+                },
+                CameraControl::Zoom,
+            )
+            .insert(
+                UserInput::chord([
+                    self.get(Control::DragPan),
+                    InputKind::DualAxis(DualAxis::mouse_motion()),
+                ]),
+                CameraControl::PanCamMouse,
+            )
+            .insert(
+                VirtualDPad {
+                    up: self.get(Control::PanUp),
+                    down: self.get(Control::PanDown),
+                    left: self.get(Control::PanLeft),
+                    right: self.get(Control::PanRight),
+                }
+                .inverted_x(),
+                CameraControl::PanCamDPad,
+            )
+            .insert(DualAxis::left_stick().inverted_x(), CameraControl::PanCamDPad)
+            .insert(
+                VirtualAxis {
+                    negative: InputKind::GamepadButton(GamepadButtonType::LeftTrigger2),
+                    positive: InputKind::GamepadButton(GamepadButtonType::RightTrigger2),
+                },
+                CameraControl::Zoom,
+            )
+            .build()
+    }
+}
+
+// How an input reads in the controls menu and on the upgrade cards.
+pub fn describe_input(input: &InputKind) -> String {
+    match input {
+        InputKind::Keyboard(KeyCode::Key1) => "1".into(),
+        InputKind::Keyboard(KeyCode::Key2) => "2".into(),
+        InputKind::Keyboard(KeyCode::Key3) => "3".into(),
+        InputKind::Keyboard(KeyCode::Key4) => "4".into(),
+        InputKind::Keyboard(KeyCode::Key5) => "5".into(),
+        InputKind::Keyboard(KeyCode::Key6) => "6".into(),
+        InputKind::Keyboard(KeyCode::Key7) => "7".into(),
+        InputKind::Keyboard(KeyCode::Key8) => "8".into(),
+        InputKind::Keyboard(KeyCode::Key9) => "9".into(),
+        InputKind::Keyboard(KeyCode::Key0) => "0".into(),
+        InputKind::Keyboard(KeyCode::Equals) => "=".into(),
+        InputKind::Keyboard(KeyCode::Minus) => "-".into(),
+        InputKind::Keyboard(key) => format!("{:?}", key),
+        InputKind::Mouse(MouseButton::Left) => "Left click".into(),
+        InputKind::Mouse(MouseButton::Right) => "Right click".into(),
+        InputKind::Mouse(MouseButton::Middle) => "Middle click".into(),
+        InputKind::Mouse(MouseButton::Other(button)) => format!("Mouse {}", button),
+        InputKind::MouseWheel(MouseWheelDirection::Up) => "Wheel up".into(),
+        InputKind::MouseWheel(MouseWheelDirection::Down) => "Wheel down".into(),
+        other => format!("{:?}", other),
+    }
+}
+
+// Where the mouse is pointing, in world coordinates.
+// A gamepad has no cursor to go by, so without one this points at the middle of the screen instead.
+#[derive(SystemParam)]
+pub struct CursorWorldPos<'w, 's> {
+    window: Query<'w, 's, &'static Window, With<PrimaryWindow>>,
+    camera: Query<'w, 's, (&'static Camera, &'static GlobalTransform), With<MainCamera>>,
+}
+impl<'w, 's> CursorWorldPos<'w, 's> {
+    pub fn get(&self) -> Option<Vec2> {
+        let (camera, camera_transform) = self.camera.get_single().ok()?;
+        let window = self.window.get_single().ok()?;
+        let cursor = window
+            .cursor_position()
+            .unwrap_or(Vec2::new(window.width(), window.height()) / 2.);
+        camera.viewport_to_world_2d(camera_transform, cursor)
+    }
+    // The world position under some other point on the screen, like a finger.
+    pub fn at(&self, screen: Vec2) -> Option<Vec2> {
+        let (camera, camera_transform) = self.camera.get_single().ok()?;
+        camera.viewport_to_world_2d(camera_transform, screen)
+    }
+}
+
+// What the pan modes want to move the camera by this frame, and the velocity it carries on with once they let go.
+#[derive(Component, Default)]
+pub struct CameraMotion {
+    pan: Vec2,
+    steering: bool,
+    velocity: Vec2,
 }
+impl CameraMotion {
+    fn steer(&mut self, pan: Vec2) {
+        self.steering = true;
+        self.pan += pan;
+    }
+    pub fn stop(&mut self) {
+        self.velocity = Vec2::ZERO;
+    }
+}
+
+// A finger held still on the gamefield for a moment, at this world position. Touchscreens' stand-in for clicking on something.
+#[derive(Event)]
+pub struct LongPress(pub Vec2);
+
+// Fingers that came down on the UI - those are pressing buttons, not moving the camera.
+#[derive(Resource, Default)]
+struct UiTouches(HashSet<u64>);
+
+type TouchCameraQuery<'w, 's> = Query<
+    'w,
+    's,
+    (
+        &'static Camera,
+        &'static GlobalTransform,
+        &'static mut OrthographicProjection,
+        &'static mut CameraMotion,
+    ),
+    With<MainCamera>,
+>;
 
 // TODO - We should really figure out a way to clean this up so that the input module doesn't have to import every UI module forever.
 fn setup(
@@ -71,65 +451,157 @@ fn setup(
 
 fn game_field_setup(
     mut commands: Commands,
+    bindings: Res<ControlBindings>,
     window: Query<Entity, With<PrimaryWindow>>,
     camera: Query<Entity, With<MainCamera>>,
 ) {
     let (win, cam) = (window.single(), camera.single());
     commands
         .entity(cam)
-        .insert(InputManagerBundle::<CameraControl> {
-            input_map: InputMap::default()
-                .insert(SingleAxis::mouse_wheel_y(), CameraControl::Zoom)
-                .insert_multiple([
-                    (
-                        UserInput::chord([
-                            InputKind::Mouse(MouseButton::Middle),
-                            InputKind::DualAxis(DualAxis::mouse_motion()),
-                        ]),
-                        CameraControl::PanCamMouse,
-                    ),
-                    (
-                        VirtualDPad::wasd().inverted_x().into(),
-                        CameraControl::PanCamDPad,
-                    ),
-                ])
-                .build(),
-            ..default()
-        });
+        .insert((
+            InputManagerBundle::<CameraControl> {
+                input_map: bindings.camera_map(),
+                ..default()
+            },
+            CameraMotion::default(),
+        ));
     commands
         .entity(win)
         .insert(InputManagerBundle::<GamefieldActions> {
-            input_map: InputMap::default()
-                .insert(MouseButton::Left, GamefieldActions::GameFieldClick)
-                .insert(KeyCode::Space, GamefieldActions::TogglePause)
-                .insert(KeyCode::Escape, GamefieldActions::OpenMainMenu)
-                .build(),
+            input_map: bindings.gamefield_map(),
             ..default()
         });
 }
 
+fn controls_changed(bindings: Res<ControlBindings>) -> bool {
+    bindings.is_changed()
+}
+
+// Picks up rebinds from the controls menu. Before the gamefield is first set up there's nothing to update yet.
+// Only the window and camera are bound to real inputs - the gamefield UI has a map of its own that's driven by its buttons.
+fn apply_control_bindings(
+    bindings: Res<ControlBindings>,
+    mut gamefield_maps: Query<&mut InputMap<GamefieldActions>, With<PrimaryWindow>>,
+    mut camera_maps: Query<&mut InputMap<CameraControl>, With<MainCamera>>,
+) {
+    for mut map in gamefield_maps.iter_mut() {
+        *map = bindings.gamefield_map();
+    }
+    for mut map in camera_maps.iter_mut() {
+        *map = bindings.camera_map();
+    }
+}
+
+fn edge_scroll_enabled(settings: Res<CameraSettings>) -> bool {
+    settings.edge_scroll
+}
+fn drag_pan_enabled(settings: Res<CameraSettings>) -> bool {
+    settings.drag_pan
+}
+
 fn zoom_camera(
-    mut query: Query<(&mut OrthographicProjection, &ActionState<CameraControl>), With<MainCamera>>,
+    settings: Res<CameraSettings>,
+    bounds: Res<WorldBounds>,
+    cursor: CursorWorldPos,
+    mut query: Query<
+        (
+            &mut OrthographicProjection,
+            &mut Transform,
+            &ActionState<CameraControl>,
+        ),
+        With<MainCamera>,
+    >,
 ) {
-    let (mut camera_projection, action_state) = query.single_mut();
+    let (mut camera_projection, mut camera_transform, action_state) = query.single_mut();
 
     let zoom_delta = action_state.value(CameraControl::Zoom);
+    if zoom_delta == 0. {
+        return;
+    }
 
-    camera_projection.scale =
-        (camera_projection.scale * 1. - zoom_delta * CAMERA_ZOOM_RATE).clamp(0.1, 20.);
+    let old_scale = camera_projection.scale;
+    camera_projection.scale = (old_scale * 1. - zoom_delta * CAMERA_ZOOM_RATE)
+        .clamp(CAMERA_MIN_SCALE, max_camera_scale(&bounds));
+
+    // Keeps whatever's under the cursor right where it is on screen.
+    if settings.zoom_to_cursor {
+        if let Some(focus) = cursor.get() {
+            let offset = camera_transform.translation.truncate() - focus;
+            let zoomed = focus + offset * (camera_projection.scale / old_scale);
+            camera_transform.translation.x = zoomed.x;
+            camera_transform.translation.y = zoomed.y;
+        }
+    }
+}
+
+fn recenter_on_nest(
+    q: Query<&ActionState<GamefieldActions>>,
+    mut camera: Query<(&mut Transform, &mut CameraMotion), With<MainCamera>>,
+    colony: Query<&ColonyPos, With<Colony>>,
+    mut inspected: ResMut<InspectedAnt>,
+) {
+    if q
+        .iter()
+        .any(|action| action.just_pressed(GamefieldActions::RecenterOnNest))
+    {
+        inspected.stop_following();
+        if let Ok(home) = colony.get_single() {
+            let (mut camera_transform, mut motion) = camera.single_mut();
+            camera_transform.translation.x = home.0.x;
+            camera_transform.translation.y = home.0.y;
+            motion.stop();
+        }
+    }
+}
+
+fn follow_inspected_ant(
+    inspected: Res<InspectedAnt>,
+    ants: Query<&GlobalTransform, With<Ant>>,
+    mut camera: Query<&mut Transform, With<MainCamera>>,
+) {
+    if let Some(ant_transform) = inspected.followed().and_then(|ant| ants.get(ant).ok()) {
+        let mut camera_transform = camera.single_mut();
+        camera_transform.translation.x = ant_transform.translation().x;
+        camera_transform.translation.y = ant_transform.translation().y;
+    }
+}
+
+// Keeps the camera over the world - no zooming out past the point where the whole world fits on screen, and no scrolling off into the void.
+fn clamp_camera_to_world(
+    mut q: Query<(&mut OrthographicProjection, &mut Transform), With<MainCamera>>,
+    bounds: Res<WorldBounds>,
+) {
+    let (mut projection, mut camera_transform) = q.single_mut();
+    let max_scale = max_camera_scale(&bounds);
+    if projection.scale > max_scale {
+        projection.scale = max_scale;
+    }
+    let clamped = camera_transform
+        .translation
+        .truncate()
+        .clamp(bounds.0.min, bounds.0.max);
+    camera_transform.translation.x = clamped.x;
+    camera_transform.translation.y = clamped.y;
+}
+
+// Zoomed out as far as this goes, the whole world fits on screen.
+fn max_camera_scale(bounds: &WorldBounds) -> f32 {
+    (bounds.0.size() / CAMERA_MIN_VIEW)
+        .max_element()
+        .clamp(CAMERA_MIN_SCALE, CAMERA_MAX_SCALE)
 }
 
 fn pan_camera(
     mut q: Query<
         (
             &OrthographicProjection,
-            &mut Transform,
             &ActionState<CameraControl>,
+            &mut CameraMotion,
         ),
-        With<Camera>,
+        With<MainCamera>,
     >,
 ) {
-    let (projection, mut camera_transform, action_state) = q.single_mut();
+    let (projection, action_state, mut motion) = q.single_mut();
     let (mousepan, dpadpan) = (
         action_state.pressed(CameraControl::PanCamMouse),
         action_state.pressed(CameraControl::PanCamDPad),
@@ -148,14 +620,246 @@ fn pan_camera(
         .action_data(action_data)
         .axis_pair
         .map(|axis_data| {
-            camera_transform.translation.x +=
-                axis_data.x() * -(CAMERA_PAN_SPEED_FACTOR * zoom_scale);
-            camera_transform.translation.y +=
-                axis_data.y() * (CAMERA_PAN_SPEED_FACTOR * zoom_scale);
+            motion.steer(Vec2::new(
+                axis_data.x() * -(CAMERA_PAN_SPEED_FACTOR * zoom_scale),
+                axis_data.y() * (CAMERA_PAN_SPEED_FACTOR * zoom_scale),
+            ));
         });
 
 }
 
+// Keeps the camera moving while the cursor rests against an edge of the window, as fast as the pan keys move it.
+fn edge_scroll_camera(
+    window: Query<&Window, With<PrimaryWindow>>,
+    mut q: Query<(&OrthographicProjection, &mut CameraMotion), With<MainCamera>>,
+) {
+    let (Ok(window), Ok((projection, mut motion))) = (window.get_single(), q.get_single_mut()) else {
+        return;
+    };
+    let Some(cursor) = window.cursor_position().filter(|_| window.focused) else {
+        return;
+    };
+    let mut direction = Vec2::ZERO;
+    if cursor.x < CAMERA_EDGE_SCROLL_MARGIN {
+        direction.x -= 1.;
+    } else if cursor.x > window.width() - CAMERA_EDGE_SCROLL_MARGIN {
+        direction.x += 1.;
+    }
+    // The window counts down from the top, the world up from the bottom.
+    if cursor.y < CAMERA_EDGE_SCROLL_MARGIN {
+        direction.y += 1.;
+    } else if cursor.y > window.height() - CAMERA_EDGE_SCROLL_MARGIN {
+        direction.y -= 1.;
+    }
+    if direction != Vec2::ZERO {
+        motion.steer(direction.normalize() * CAMERA_PAN_SPEED_FACTOR * projection.scale * 0.5);
+    }
+}
+
+// South both selects on the gamefield and presses the focused button - while a button has focus, it's only the latter.
+fn hold_click_for_focused_button(
+    focused: Res<FocusedButton>,
+    mut actions: Query<&mut ActionState<GamefieldActions>>,
+) {
+    if focused.entity().is_none() {
+        return;
+    }
+    for mut action in actions.iter_mut() {
+        if action.pressed(GamefieldActions::GameFieldClick) {
+            action.consume(GamefieldActions::GameFieldClick);
+        }
+    }
+}
+
+// Grabs the ground under the cursor and keeps it there for as long as the click is held.
+// Presses on the UI or on an ant are left alone - those clicks are meant for something else.
+fn drag_pan_camera(
+    mut grabbed: Local<Option<Vec2>>,
+    actions: Query<&ActionState<GamefieldActions>, With<PrimaryWindow>>,
+    window: Query<&Window, With<PrimaryWindow>>,
+    mut camera: Query<(&Camera, &GlobalTransform, &mut CameraMotion), With<MainCamera>>,
+    ui: Query<&Interaction>,
+    ants: Res<KDTree2<AntSpatialMarker>>,
+) {
+    let Ok(action) = actions.get_single() else {
+        return;
+    };
+    if !action.pressed(GamefieldActions::GameFieldClick) {
+        *grabbed = None;
+        return;
+    }
+    let (Ok(window), Ok((camera, camera_transform, mut motion))) =
+        (window.get_single(), camera.get_single_mut())
+    else {
+        return;
+    };
+    let Some(cursor) = window
+        .cursor_position()
+        .and_then(|cursor| camera.viewport_to_world_2d(camera_transform, cursor))
+    else {
+        return;
+    };
+    if action.just_pressed(GamefieldActions::GameFieldClick) {
+        let on_ui = ui
+            .iter()
+            .any(|interaction| !matches!(interaction, Interaction::None));
+        let on_ant = ants
+            .nearest_neighbour(cursor)
+            .is_some_and(|(ant_pos, _)| ant_pos.distance(cursor) <= PICK_RADIUS);
+        *grabbed = (!on_ui && !on_ant).then_some(cursor);
+    }
+    if let Some(anchor) = *grabbed {
+        motion.steer(anchor - cursor);
+    }
+}
+
+type InteractiveNodeQuery<'w, 's> = Query<
+    'w,
+    's,
+    (
+        &'static Node,
+        &'static GlobalTransform,
+        &'static ViewVisibility,
+        Option<&'static CalculatedClip>,
+    ),
+    With<Interaction>,
+>;
+
+// A finger belongs to the UI if it came down on a button, whatever any other finger or the mouse is up to.
+fn track_ui_touches(
+    touches: Res<Touches>,
+    ui_scale: Res<UiScale>,
+    nodes: InteractiveNodeQuery,
+    mut ui_touches: ResMut<UiTouches>,
+) {
+    ui_touches.0.retain(|finger| touches.get_pressed(*finger).is_some());
+    for touch in touches.iter_just_pressed() {
+        let at = touch.position() / ui_scale.0 as f32;
+        let on_ui = nodes
+            .iter()
+            .filter(|(_, _, visibility, _)| visibility.get())
+            .any(|(node, transform, _, clip)| {
+                let rect = node.logical_rect(transform);
+                clip.map_or(rect, |clip| rect.intersect(clip.clip))
+                    .contains(at)
+            });
+        if on_ui {
+            ui_touches.0.insert(touch.id());
+        }
+    }
+}
+
+fn gamefield_fingers<'a>(touches: &'a Touches, ui_touches: &'a UiTouches) -> Vec<&'a Touch> {
+    touches
+        .iter()
+        .filter(|touch| !ui_touches.0.contains(&touch.id()))
+        .collect()
+}
+
+// One finger drags the ground along with it. Two pinch to zoom around the spot between them, and drag the camera as they go.
+fn touch_camera(
+    touches: Res<Touches>,
+    ui_touches: Res<UiTouches>,
+    bounds: Res<WorldBounds>,
+    mut camera: TouchCameraQuery,
+) {
+    let Ok((camera, camera_transform, mut projection, mut motion)) = camera.get_single_mut() else {
+        return;
+    };
+    let to_world = |screen: Vec2| camera.viewport_to_world_2d(camera_transform, screen);
+    match gamefield_fingers(&touches, &ui_touches).as_slice() {
+        [] => {}
+        [finger] => {
+            if let (Some(from), Some(to)) = (
+                to_world(finger.previous_position()),
+                to_world(finger.position()),
+            ) {
+                motion.steer(from - to);
+            }
+        }
+        [first, second, ..] => {
+            let before = first.previous_position().distance(second.previous_position());
+            let after = first.position().distance(second.position());
+            if before <= 0. || after <= 0. {
+                return;
+            }
+            let old_scale = projection.scale;
+            projection.scale =
+                (old_scale * before / after).clamp(CAMERA_MIN_SCALE, max_camera_scale(&bounds));
+            let midpoint_before = (first.previous_position() + second.previous_position()) / 2.;
+            let midpoint_after = (first.position() + second.position()) / 2.;
+            // Whatever was between the fingers stays between them.
+            if let (Some(anchor), Some(under)) =
+                (to_world(midpoint_before), to_world(midpoint_after))
+            {
+                let camera_pos = camera_transform.translation().truncate();
+                let zoomed = anchor - (under - camera_pos) * (projection.scale / old_scale);
+                motion.steer(zoomed - camera_pos);
+            }
+        }
+    }
+}
+
+fn touch_long_press(
+    mut pending: Local<Option<(u64, f32)>>,
+    touches: Res<Touches>,
+    ui_touches: Res<UiTouches>,
+    time: Res<Time>,
+    cursor: CursorWorldPos,
+    mut long_presses: EventWriter<LongPress>,
+) {
+    let [finger] = gamefield_fingers(&touches, &ui_touches)[..] else {
+        *pending = None;
+        return;
+    };
+    if touches.just_pressed(finger.id()) {
+        *pending = Some((finger.id(), 0.));
+    }
+    let Some((id, held)) = pending.as_mut() else {
+        return;
+    };
+    if *id != finger.id() || finger.distance().length() > LONG_PRESS_SLOP {
+        *pending = None;
+        return;
+    }
+    *held += time.delta_seconds();
+    if *held >= LONG_PRESS_SECS {
+        if let Some(pos) = cursor.at(finger.position()) {
+            long_presses.send(LongPress(pos));
+        }
+        *pending = None;
+    }
+}
+
+// Moves the camera by whatever the pan modes asked for. With inertia on, it glides to a stop once they've all let go.
+fn glide_camera(
+    settings: Res<CameraSettings>,
+    time: Res<Time>,
+    mut q: Query<(&mut Transform, &mut CameraMotion), With<MainCamera>>,
+) {
+    let (mut camera_transform, mut motion) = q.single_mut();
+    let delta = time.delta_seconds();
+    let step = if motion.steering {
+        if delta > 0. {
+            let velocity = motion.pan / delta;
+            motion.velocity = motion.velocity.lerp(velocity, CAMERA_GLIDE_SMOOTHING);
+        }
+        motion.pan
+    } else if settings.inertia {
+        motion.velocity *= (-CAMERA_GLIDE_DRAG * delta).exp();
+        if motion.velocity.length() < CAMERA_GLIDE_STOP {
+            motion.stop();
+        }
+        motion.velocity * delta
+    } else {
+        motion.stop();
+        Vec2::ZERO
+    };
+    camera_transform.translation += step.extend(0.);
+    motion.pan = Vec2::ZERO;
+    motion.steering = false;
+}
+
 fn user_toggle_pause(
     q: Query<&ActionState<GamefieldActions>>,
     mut sim_next: ResMut<NextState<SimState>>,
@@ -167,6 +871,7 @@ fn user_toggle_pause(
             sim_next.set(match sim_current.get() {
                 SimState::Paused | SimState::MenuOpenedWhilePaused => SimState::Playing,
                 SimState::Playing => SimState::Paused,
+                SimState::GameOver => SimState::GameOver,
             });
         }
     }
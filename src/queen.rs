@@ -0,0 +1,161 @@
+use bevy::prelude::*;
+
+use crate::{
+    colony::Colony, food::FoodQuant, gametimer::GameClock, weather::Weather, SimState, SoundScape,
+    UIFocus,
+};
+
+pub struct QueenPlugin;
+
+impl Plugin for QueenPlugin {
+    fn build(&self, app: &mut App) {
+        app.register_type::<QueenSettings>()
+            .insert_resource(QueenSettings::default())
+            .add_systems(OnEnter(UIFocus::Gamefield), spawn_queen.run_if(run_once()))
+            .add_systems(
+                Update,
+                (feed_queen, queen_death)
+                    .chain()
+                    .run_if(in_state(SimState::Playing)),
+            );
+    }
+}
+
+const QUEEN_SCALE: f32 = 2.0;
+
+#[derive(Resource, Reflect)]
+#[reflect(Resource)]
+pub struct QueenSettings {
+    pub max_health: f32,
+    pub food_per_minute: f32,
+    // Eggs laid per sim-minute while she's fed. This is what the fertility upgrade raises.
+    pub eggs_per_minute: f32,
+    // She won't hold more eggs than this waiting for the nursemaids to make room.
+    pub clutch_size: f32,
+    pub starve_damage_per_minute: f32,
+    pub heal_per_minute: f32,
+}
+impl Default for QueenSettings {
+    fn default() -> Self {
+        QueenSettings {
+            max_health: 100.0,
+            food_per_minute: 3.0,
+            eggs_per_minute: 2.0,
+            clutch_size: 4.0,
+            starve_damage_per_minute: 20.0,
+            heal_per_minute: 10.0,
+        }
+    }
+}
+
+#[derive(Component)]
+pub struct Queen {
+    colony: Entity,
+    health: f32,
+    // Fractional food and eggs carried over between frames.
+    hunger: f32,
+    eggs: f32,
+    starving: bool,
+}
+impl Queen {
+    pub fn new(colony: Entity, settings: &QueenSettings) -> Self {
+        Queen {
+            colony,
+            health: settings.max_health,
+            hunger: 0.0,
+            // She starts with a full clutch so the first larva don't have to wait.
+            eggs: settings.clutch_size,
+            starving: false,
+        }
+    }
+    pub fn health(&self) -> f32 {
+        self.health
+    }
+    pub fn eggs_ready(&self) -> i32 {
+        self.eggs.floor() as i32
+    }
+    pub fn is_starving(&self) -> bool {
+        self.starving
+    }
+    // Takes one egg from her, if she has one ready.
+    pub fn lay_egg(&mut self) -> bool {
+        if self.eggs < 1.0 {
+            return false;
+        }
+        self.eggs -= 1.0;
+        true
+    }
+}
+
+fn spawn_queen(
+    mut commands: Commands,
+    assets: Res<AssetServer>,
+    settings: Res<QueenSettings>,
+    q: Query<Entity, With<Colony>>,
+) {
+    for colony in q.iter() {
+        let mut transform = Transform::from_xyz(0., -4., 0.2);
+        transform.scale = Vec3::splat(QUEEN_SCALE);
+        commands.spawn((
+            SpriteBundle {
+                texture: assets.load("ant.png"),
+                sprite: Sprite {
+                    color: Color::rgb_u8(217, 160, 102),
+                    ..default()
+                },
+                transform,
+                ..default()
+            },
+            Queen::new(colony, &settings),
+            Name::new("Queen"),
+        ));
+    }
+}
+
+// The queen eats from the colony stores ahead of everyone else. Fed, she heals and lays. Hungry, she wastes away.
+fn feed_queen(
+    clock: Res<GameClock>,
+    settings: Res<QueenSettings>,
+    weather: Res<Weather>,
+    mut queen_q: Query<&mut Queen>,
+    mut col_q: Query<&mut FoodQuant, With<Colony>>,
+) {
+    let minutes = clock.delta.as_secs_f32() / 60.0;
+    for mut queen in queen_q.iter_mut() {
+        let Ok(mut food) = col_q.get_mut(queen.colony) else {
+            continue;
+        };
+        queen.hunger += settings.food_per_minute * weather.upkeep_factor() * minutes;
+        let owed = queen.hunger.floor() as i32;
+        if owed > 0 {
+            queen.hunger -= owed as f32;
+            queen.starving = food.0 < owed;
+            food.0 = (food.0 - owed).max(0);
+        }
+        if queen.starving {
+            queen.health -= settings.starve_damage_per_minute * minutes;
+        } else {
+            queen.health =
+                (queen.health + settings.heal_per_minute * minutes).min(settings.max_health);
+            queen.eggs =
+                (queen.eggs + settings.eggs_per_minute * minutes).min(settings.clutch_size);
+        }
+    }
+}
+
+// No queen, no more eggs - the colony is done for.
+fn queen_death(
+    mut commands: Commands,
+    mut sounds: EventWriter<SoundScape>,
+    mut sim_state: ResMut<NextState<SimState>>,
+    queen_q: Query<(Entity, &Queen)>,
+) {
+    for (entity, queen) in queen_q.iter() {
+        if queen.health <= 0.0 {
+            info!("the queen has died, game over");
+            commands.entity(entity).despawn_recursive();
+            sounds.send(SoundScape::AntDeath);
+            sim_state.set(SimState::GameOver);
+        }
+    }
+}
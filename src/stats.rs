@@ -0,0 +1,130 @@
+use std::collections::VecDeque;
+
+use bevy::prelude::*;
+
+use crate::{
+    ant::{ForagerAnt, IdleAnt, NursemaidAnt},
+    colony::{AntPopulation, Colony, LaborData},
+    food::{FoodDeltaEvent, FoodQuant},
+    gametimer::GameClock,
+    SimState,
+};
+
+pub struct StatsPlugin;
+
+impl Plugin for StatsPlugin {
+    fn build(&self, app: &mut App) {
+        app.add_event::<ColonyEvent>()
+            .init_resource::<ColonyStats>()
+            .add_systems(
+                Update,
+                (count_colony_events, record_stats)
+                    .chain()
+                    .run_if(in_state(SimState::Playing)),
+            );
+    }
+}
+
+// Sim-seconds between samples, and how many we hang on to - an hour of sim time.
+const SAMPLE_INTERVAL: f32 = 15.0;
+const MAX_SAMPLES: usize = 240;
+
+// Things that happen to the colony that we want to count, but that don't leave anything behind to query for afterwards.
+#[derive(Event, Clone, Copy)]
+pub enum ColonyEvent {
+    AntBorn,
+    AntDied,
+}
+
+#[derive(Clone, Copy, Default)]
+pub struct StatSample {
+    pub food: i32,
+    pub population: i32,
+    pub births_per_minute: f32,
+    pub deaths_per_minute: f32,
+    pub deliveries_per_minute: f32,
+    pub foragers: i32,
+    pub nursemaids: i32,
+    pub idle: i32,
+}
+
+#[derive(Resource, Default)]
+pub struct ColonyStats {
+    samples: VecDeque<StatSample>,
+    // Total samples ever taken, so the UI knows when there's something new to draw.
+    taken: usize,
+    since_sample: f32,
+    births: u32,
+    deaths: u32,
+    deliveries: u32,
+}
+impl ColonyStats {
+    pub fn samples(&self) -> &VecDeque<StatSample> {
+        &self.samples
+    }
+    pub fn taken(&self) -> usize {
+        self.taken
+    }
+}
+
+fn count_colony_events(
+    mut stats: ResMut<ColonyStats>,
+    mut colony_events: EventReader<ColonyEvent>,
+    mut food_events: EventReader<FoodDeltaEvent>,
+    colonies: Query<(), With<Colony>>,
+) {
+    for event in colony_events.read() {
+        match event {
+            ColonyEvent::AntBorn => stats.births += 1,
+            ColonyEvent::AntDied => stats.deaths += 1,
+        }
+    }
+    let deliveries = food_events
+        .read()
+        // Ants that come home empty-handed still hand over their (empty) load, which isn't a delivery.
+        .filter(|event| event.requested > 0 && colonies.contains(event.food_to))
+        .count();
+    stats.deliveries += deliveries as u32;
+}
+
+fn record_stats(
+    clock: Res<GameClock>,
+    mut stats: ResMut<ColonyStats>,
+    col_q: Query<(&FoodQuant, &AntPopulation), With<Colony>>,
+    labor_q: Query<(
+        &LaborData<ForagerAnt>,
+        &LaborData<NursemaidAnt>,
+        &LaborData<IdleAnt>,
+    )>,
+) {
+    stats.since_sample += clock.delta.as_secs_f32();
+    // Always take one straight away, so there's something on the graphs from the start.
+    if stats.since_sample < SAMPLE_INTERVAL && stats.taken > 0 {
+        return;
+    }
+    let (Ok((food, population)), Ok((foragers, nursemaids, idle))) =
+        (col_q.get_single(), labor_q.get_single())
+    else {
+        return;
+    };
+    let minutes = (stats.since_sample / 60.0).max(f32::EPSILON);
+    let sample = StatSample {
+        food: food.0,
+        population: population.0,
+        births_per_minute: stats.births as f32 / minutes,
+        deaths_per_minute: stats.deaths as f32 / minutes,
+        deliveries_per_minute: stats.deliveries as f32 / minutes,
+        foragers: foragers.active,
+        nursemaids: nursemaids.active,
+        idle: idle.active,
+    };
+    if stats.samples.len() >= MAX_SAMPLES {
+        stats.samples.pop_front();
+    }
+    stats.samples.push_back(sample);
+    stats.taken += 1;
+    stats.since_sample = 0.0;
+    stats.births = 0;
+    stats.deaths = 0;
+    stats.deliveries = 0;
+}
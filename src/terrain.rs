@@ -0,0 +1,160 @@
+use bevy::{math::Vec3Swizzles, prelude::*, sprite::MaterialMesh2dBundle};
+use rand::Rng;
+
+use crate::{nav::DistanceAwareQuery, ObstacleSpatialMarker};
+
+pub struct TerrainPlugin;
+
+impl Plugin for TerrainPlugin {
+    fn build(&self, app: &mut App) {
+        app.register_type::<Obstacle>();
+    }
+}
+
+// Anything bigger than this won't be found by a spatial lookup that only pads by this much.
+pub const OBSTACLE_MAX_RADIUS: f32 = 40.0;
+// Ants keep this much clearance between themselves and the edge of an obstacle.
+pub const OBSTACLE_CLEARANCE: f32 = 4.0;
+
+#[derive(Reflect, Clone, Copy, Debug, PartialEq, Eq)]
+pub enum ObstacleKind {
+    Rock,
+    Water,
+    LeafLitter,
+}
+impl ObstacleKind {
+    pub fn random(rng: &mut impl Rng) -> Self {
+        match rng.gen_range(0..3) {
+            0 => ObstacleKind::Rock,
+            1 => ObstacleKind::Water,
+            _ => ObstacleKind::LeafLitter,
+        }
+    }
+    fn color(&self) -> Color {
+        match self {
+            ObstacleKind::Rock => Color::rgb_u8(105, 106, 106),
+            ObstacleKind::Water => Color::rgb_u8(91, 110, 225),
+            ObstacleKind::LeafLitter => Color::rgb_u8(143, 86, 59),
+        }
+    }
+    pub fn radius_range(&self) -> (f32, f32) {
+        match self {
+            ObstacleKind::Rock => (8.0, 25.0),
+            ObstacleKind::Water => (20.0, OBSTACLE_MAX_RADIUS),
+            ObstacleKind::LeafLitter => (10.0, 20.0),
+        }
+    }
+}
+
+#[derive(Component, Reflect, Clone, Copy)]
+pub struct Obstacle {
+    pub kind: ObstacleKind,
+    pub radius: f32,
+}
+impl Obstacle {
+    pub fn contains(&self, center: Vec2, point: Vec2, padding: f32) -> bool {
+        center.distance(point) < self.radius + padding
+    }
+}
+
+// Read-only view over every obstacle close enough to matter for a given position.
+pub type ObstacleQuery<'w, 's> = DistanceAwareQuery<
+    'w,
+    's,
+    ObstacleSpatialMarker,
+    (&'static GlobalTransform, &'static Obstacle),
+>;
+
+pub fn obstacles_near<'a>(
+    obstacles: &'a ObstacleQuery,
+    pos: Vec2,
+    distance: f32,
+) -> impl Iterator<Item = (Vec2, Obstacle)> + 'a {
+    obstacles
+        .within_distance(pos, distance + OBSTACLE_MAX_RADIUS)
+        .map(|(transform, obstacle)| (transform.translation().xy(), *obstacle))
+}
+
+pub fn clear_of_obstacles(obstacles: &ObstacleQuery, pos: Vec2, padding: f32) -> bool {
+    !obstacles_near(obstacles, pos, padding)
+        .any(|(center, obstacle)| obstacle.contains(center, pos, padding))
+}
+
+// Finds the nearest obstacle sitting on the straight line between `from` and `to`, and returns a point just past its edge to steer for instead.
+pub fn detour_around(
+    obstacles: &ObstacleQuery,
+    from: Vec2,
+    to: Vec2,
+    lookahead: f32,
+) -> Option<Vec2> {
+    let dir = (to - from).normalize_or_zero();
+    if dir == Vec2::ZERO {
+        return None;
+    }
+    let reach = from.distance(to).min(lookahead);
+
+    let mut nearest: Option<(f32, Vec2, f32)> = None;
+    for (center, obstacle) in obstacles_near(obstacles, from, reach) {
+        let clearance = obstacle.radius + OBSTACLE_CLEARANCE;
+        let to_center = center - from;
+        let along = to_center.dot(dir);
+        if along <= 0.0 || along - clearance > reach {
+            continue;
+        }
+        let closest_approach = dir * along - to_center;
+        if closest_approach.length() >= clearance {
+            continue;
+        }
+        if !nearest.is_some_and(|(best, _, _)| best <= along) {
+            nearest = Some((along, center, clearance));
+        }
+    }
+
+    nearest.map(|(_, center, clearance)| {
+        let closest_approach = from + dir * (center - from).dot(dir) - center;
+        // Dead center - pick a side, any side.
+        let side = if closest_approach.length() < f32::EPSILON {
+            dir.perp()
+        } else {
+            closest_approach.normalize()
+        };
+        center + side * (clearance + OBSTACLE_CLEARANCE)
+    })
+}
+
+// If something has ended up inside an obstacle, shove it back out to the nearest edge.
+pub fn push_out_of_obstacles(obstacles: &ObstacleQuery, pos: Vec2, padding: f32) -> Vec2 {
+    let mut res = pos;
+    for (center, obstacle) in obstacles_near(obstacles, pos, padding) {
+        if obstacle.contains(center, res, padding) {
+            let away = (res - center).normalize_or_zero();
+            let away = if away == Vec2::ZERO { Vec2::Y } else { away };
+            res = center + away * (obstacle.radius + padding);
+        }
+    }
+    res
+}
+
+pub fn spawn_obstacle(
+    commands: &mut Commands,
+    meshes: &mut Assets<Mesh>,
+    materials: &mut Assets<ColorMaterial>,
+    obstacle: Obstacle,
+    pos: Vec2,
+) -> Entity {
+    commands
+        .spawn((
+            MaterialMesh2dBundle {
+                mesh: meshes
+                    .add(shape::Circle::new(obstacle.radius).into())
+                    .into(),
+                material: materials.add(ColorMaterial::from(obstacle.kind.color())),
+                transform: Transform::from_xyz(pos.x, pos.y, 0.05),
+                ..default()
+            },
+            obstacle,
+            ObstacleSpatialMarker,
+            Name::new(format!("{:?}", obstacle.kind)),
+        ))
+        .id()
+}
@@ -0,0 +1,202 @@
+use std::{collections::HashMap, f32::consts::PI};
+
+use bevy::{math::Vec3Swizzles, prelude::*};
+
+use crate::{
+    ant::{Ant, ForagerAnt, Navigate, NavigationSystems},
+    colony::{nearest_entrance, EntranceQuery},
+    food::{FoodDeltaEvent, FoodQuant},
+    gametimer::SimTimer,
+    SimState, SpatialMarker,
+};
+
+pub struct TransportPlugin;
+
+impl Plugin for TransportPlugin {
+    fn build(&self, app: &mut App) {
+        app.register_type::<HeavyFood>().add_systems(
+            Update,
+            haul_heavy_food
+                .after(NavigationSystems)
+                .run_if(in_state(SimState::Playing)),
+        );
+    }
+}
+
+// Haulers hold on this far from the middle of whatever they're dragging.
+pub const HEAVY_FOOD_GRIP_RADIUS: f32 = 10.0;
+pub const HEAVY_FOOD_GRIP_TOLERANCE: f32 = 3.0;
+pub const HEAVY_FOOD_CHANCE: f64 = 0.15;
+pub const HEAVY_FOOD_PER_CARRIER: i32 = 200;
+const HEAVY_FOOD_BASE_SPEED: f32 = 1.5;
+const HEAVY_FOOD_MAX_SPEED: f32 = 4.5;
+const HEAVY_FOOD_MAX_CARRIER_FACTOR: usize = 2;
+const HEAVY_FOOD_DELIVERY_RADIUS: f32 = 15.0;
+const HEAVY_FOOD_SCALE: f32 = 0.6;
+
+// Something that can't be split up and carried off a bite at a time, like a dead beetle.
+// Nobody can budge it until `carriers_needed` ants have hold of it - past that, each extra hauler speeds things up.
+#[derive(Component, Reflect)]
+pub struct HeavyFood {
+    pub carriers_needed: usize,
+    pub carriers: usize,
+}
+impl HeavyFood {
+    pub fn is_full(&self) -> bool {
+        self.carriers >= self.carriers_needed * HEAVY_FOOD_MAX_CARRIER_FACTOR
+    }
+    pub fn is_gripping(&self, item_pos: Vec2, ant_pos: Vec2) -> bool {
+        (item_pos.distance(ant_pos) - HEAVY_FOOD_GRIP_RADIUS).abs() <= HEAVY_FOOD_GRIP_TOLERANCE
+    }
+    fn speed(&self) -> f32 {
+        (HEAVY_FOOD_BASE_SPEED * self.carriers as f32 / self.carriers_needed as f32)
+            .min(HEAVY_FOOD_MAX_SPEED)
+    }
+}
+
+// Set on a forager that got tired of waiting for a crew on this item. It won't grab hold of it again until it's been home.
+#[derive(Component)]
+pub struct GaveUpHauling(pub Option<Entity>);
+
+// The spot on the rim of the item closest to where the ant is coming from.
+pub fn heavy_food_grip_point(item_pos: Vec2, ant_pos: Vec2) -> Vec2 {
+    let dir = (ant_pos - item_pos).normalize_or_zero();
+    let dir = if dir == Vec2::ZERO { Vec2::Y } else { dir };
+    item_pos + dir * HEAVY_FOOD_GRIP_RADIUS
+}
+
+pub fn spawn_heavy_food(
+    commands: &mut Commands,
+    assets: &AssetServer,
+    pos: Vec2,
+    carriers_needed: usize,
+) -> Entity {
+    let texture = assets.load("food_chunk.png");
+    let mut transform = Transform::from_xyz(pos.x, pos.y, 0.1);
+    transform.scale = Vec3::from((HEAVY_FOOD_SCALE, HEAVY_FOOD_SCALE, 1.0));
+    commands
+        .spawn((
+            FoodQuant(carriers_needed as i32 * HEAVY_FOOD_PER_CARRIER),
+            HeavyFood {
+                carriers_needed,
+                carriers: 0,
+            },
+            Navigate::new(0.0, PI),
+            SpriteBundle {
+                sprite: Sprite {
+                    color: Color::rgb_u8(69, 40, 60),
+                    ..default()
+                },
+                texture,
+                transform,
+                ..default()
+            },
+            SpatialMarker,
+            Name::new("Beetle"),
+        ))
+        .id()
+}
+
+// Runs after navigation so that the crew gets dragged along with wherever the item ended up this frame.
+fn haul_heavy_food(
+    mut commands: Commands,
+    mut items: Query<
+        (
+            Entity,
+            &Transform,
+            &FoodQuant,
+            &mut HeavyFood,
+            &mut Navigate,
+        ),
+        Without<Ant>,
+    >,
+    mut haulers: Query<
+        (Entity, &Ant, &mut ForagerAnt, &mut Transform, &mut SimTimer),
+        Without<HeavyFood>,
+    >,
+    mut tints: Query<&mut Sprite, With<HeavyFood>>,
+    mut foodevents: EventWriter<FoodDeltaEvent>,
+    entrances: EntranceQuery,
+) {
+    let mut crews: HashMap<Entity, Vec<Entity>> = HashMap::new();
+    for (entity, _, behavior, _, _) in haulers.iter() {
+        if let ForagerAnt::Hauling(item) = *behavior {
+            crews.entry(item).or_default().push(entity);
+        }
+    }
+
+    for (entity, transform, food_q, mut heavy, mut nav) in items.iter_mut() {
+        let item_pos = transform.translation.xy();
+        let mut crew = crews.remove(&entity).unwrap_or_default();
+        crew.retain(|ant| {
+            haulers.get(*ant).is_ok_and(|(_, _, _, xform, _)| {
+                heavy.is_gripping(item_pos, xform.translation.xy())
+            })
+        });
+        heavy.carriers = crew.len();
+
+        let Some((home, colony)) =
+            crew.first()
+                .and_then(|ant| haulers.get(*ant).ok())
+                .map(|(_, ant, _, _, _)| {
+                    let home =
+                        nearest_entrance(&entrances, ant.colony(), item_pos).unwrap_or(ant.home());
+                    (home, ant.colony())
+                })
+        else {
+            nav.clear();
+            continue;
+        };
+
+        if item_pos.distance(home) <= HEAVY_FOOD_DELIVERY_RADIUS {
+            info!("hauled {:?} food home with {:?} ants", food_q.0, crew.len());
+            foodevents.send(FoodDeltaEvent {
+                requested: food_q.0,
+                food_from: entity,
+                food_to: colony,
+            });
+            // Whatever doesn't fit in the larder is left by the nest as an ordinary pile, so it loses the beetle tint and scale_food sizes it from here on.
+            commands.entity(entity).remove::<(HeavyFood, Navigate)>();
+            if let Ok(mut sprite) = tints.get_mut(entity) {
+                sprite.color = Color::default();
+            }
+            for ant in crew {
+                if let Ok((_, _, mut behavior, _, _)) = haulers.get_mut(ant) {
+                    *behavior = ForagerAnt::default();
+                }
+            }
+            continue;
+        }
+
+        if heavy.carriers < heavy.carriers_needed {
+            nav.clear();
+            // The seek timer doubles as how long we'll wait for help - when it runs out, let go and find something we can manage.
+            crew.retain(|ant| {
+                let Ok((_, _, mut behavior, _, mut seek_timer)) = haulers.get_mut(*ant) else {
+                    return false;
+                };
+                if !seek_timer.time.finished() {
+                    return true;
+                }
+                seek_timer.time.reset();
+                *behavior = ForagerAnt::default();
+                commands.entity(*ant).insert(GaveUpHauling(Some(entity)));
+                false
+            });
+        } else {
+            nav.set_max_speed(heavy.speed());
+            nav.set_destination(home);
+        }
+
+        for ant in crew {
+            if let Ok((_, _, _, mut xform, _)) = haulers.get_mut(ant) {
+                let grip = heavy_food_grip_point(item_pos, xform.translation.xy());
+                xform.translation = grip.extend(xform.translation.z);
+                let facing = (item_pos - grip).normalize_or_zero();
+                if facing != Vec2::ZERO {
+                    xform.rotation = Quat::from_rotation_arc_2d(Vec2::Y, facing);
+                }
+            }
+        }
+    }
+}
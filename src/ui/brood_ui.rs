@@ -0,0 +1,230 @@
+use bevy::{prelude::*, utils::HashSet};
+use bevy_nine_slice_ui::*;
+
+use super::{gamefield_ui::GamefieldUIRoot, ui_util::*};
+use crate::{
+    larva::{BroodReport, BroodStage, Larva, PausedBrood},
+    queen::Queen,
+};
+
+pub struct BroodChamberPlugin;
+
+impl Plugin for BroodChamberPlugin {
+    fn build(&self, app: &mut App) {
+        app.add_systems(
+            Update,
+            (spawn_brood_chamber, sync_brood_icons, update_brood_icons).chain(),
+        );
+    }
+}
+
+const LARVA_ICON_MIN_SIZE: f32 = 10.0;
+const LARVA_ICON_MAX_SIZE: f32 = 26.0;
+const NURSE_ICON_SIZE: f32 = 10.0;
+
+#[derive(Component)]
+struct BroodChamberGrid;
+#[derive(Component)]
+struct BroodChamberHeader;
+#[derive(Component)]
+struct LarvaIcon {
+    larva: Entity,
+}
+#[derive(Component)]
+struct NurseIcon;
+
+// An inset view of the brood chamber, tucked into the bottom left corner of the gamefield UI.
+fn spawn_brood_chamber(
+    mut commands: Commands,
+    asset_server: Res<AssetServer>,
+    root: Query<Entity, Added<GamefieldUIRoot>>,
+) {
+    let Ok(root) = root.get_single() else {
+        return;
+    };
+    let panel = commands
+        .spawn((
+            NineSliceUiMaterialBundle {
+                style: Style {
+                    position_type: PositionType::Absolute,
+                    left: px(8.),
+                    bottom: px(8.),
+                    width: px(220.),
+                    min_height: px(60.),
+                    padding: UiRect::all(px(6.)),
+                    flex_direction: FlexDirection::Column,
+                    row_gap: px(4.),
+                    ..default()
+                },
+                nine_slice_texture: NineSliceUiTexture::from_image(
+                    asset_server.load("nine_slice/bgW_bG_soft_corner_2.png"),
+                ),
+                ..default()
+            },
+            Name::new("Brood Chamber"),
+        ))
+        .id();
+    let header = commands
+        .make_text("Brood Chamber", TextStyle::local(MEDIUM, Color::BLACK))
+        .insert(BroodChamberHeader)
+        .id();
+    let grid = commands
+        .spawn((
+            NodeBundle {
+                style: Style {
+                    width: ALL,
+                    flex_direction: FlexDirection::Row,
+                    flex_wrap: FlexWrap::Wrap,
+                    align_items: AlignItems::End,
+                    column_gap: px(4.),
+                    row_gap: px(4.),
+                    ..default()
+                },
+                ..default()
+            },
+            BroodChamberGrid,
+        ))
+        .id();
+    commands.entity(root).add_child(panel);
+    commands.entity(panel).push_children(&[header, grid]);
+}
+
+// One icon per larva - add icons for new larva and clear out the ones whose larva have hatched or gone.
+fn sync_brood_icons(
+    mut commands: Commands,
+    asset_server: Res<AssetServer>,
+    grid_q: Query<Entity, With<BroodChamberGrid>>,
+    larva_q: Query<Entity, With<Larva>>,
+    icons: Query<(Entity, &LarvaIcon)>,
+) {
+    let Ok(grid) = grid_q.get_single() else {
+        return;
+    };
+    let mut shown = HashSet::new();
+    for (icon_entity, icon) in icons.iter() {
+        if larva_q.contains(icon.larva) {
+            shown.insert(icon.larva);
+        } else {
+            commands.entity(icon_entity).despawn_recursive();
+        }
+    }
+    for larva in larva_q.iter().filter(|larva| !shown.contains(larva)) {
+        let icon = commands
+            .spawn((
+                ImageBundle {
+                    image: UiImage {
+                        texture: asset_server.load("egg_icon.png"),
+                        ..default()
+                    },
+                    style: Style {
+                        width: px(LARVA_ICON_MIN_SIZE),
+                        height: px(LARVA_ICON_MIN_SIZE),
+                        ..default()
+                    },
+                    ..default()
+                },
+                LarvaIcon { larva },
+            ))
+            .id();
+        let nurse = commands
+            .spawn((
+                ImageBundle {
+                    image: UiImage {
+                        texture: asset_server.load("ant_icon.png"),
+                        ..default()
+                    },
+                    style: Style {
+                        position_type: PositionType::Absolute,
+                        right: px(-NURSE_ICON_SIZE * 0.5),
+                        bottom: px(0.),
+                        width: px(NURSE_ICON_SIZE),
+                        height: px(NURSE_ICON_SIZE),
+                        ..default()
+                    },
+                    visibility: Visibility::Hidden,
+                    ..default()
+                },
+                NurseIcon,
+            ))
+            .id();
+        commands.entity(icon).add_child(nurse);
+        commands.entity(grid).add_child(icon);
+    }
+}
+
+// Brood grow on screen as they grow in the sim, and are tinted by stage. Tended ones show a nursemaid, paused ones are greyed out,
+// and hungry ones go red.
+fn update_brood_icons(
+    larva_q: Query<(&Larva, Has<PausedBrood>)>,
+    report: Res<BroodReport>,
+    mut icons: Query<(&LarvaIcon, &mut Style, &mut BackgroundColor, &Children)>,
+    mut nurse_q: Query<&mut Visibility, With<NurseIcon>>,
+    mut header: Query<&mut Text, With<BroodChamberHeader>>,
+    queen_q: Query<&Queen>,
+) {
+    for (icon, mut style, mut color, children) in icons.iter_mut() {
+        let Ok((larva, paused)) = larva_q.get(icon.larva) else {
+            continue;
+        };
+        let size =
+            LARVA_ICON_MIN_SIZE + (LARVA_ICON_MAX_SIZE - LARVA_ICON_MIN_SIZE) * larva.progress();
+        style.width = px(size);
+        style.height = px(size);
+        *color = if paused {
+            Color::GRAY.into()
+        } else if larva.is_starving() {
+            RED().into()
+        } else {
+            stage_color(larva.stage()).into()
+        };
+        for child in children.iter() {
+            if let Ok(mut visibility) = nurse_q.get_mut(*child) {
+                *visibility = if paused {
+                    Visibility::Hidden
+                } else {
+                    Visibility::Inherited
+                };
+            }
+        }
+    }
+
+    let count = |stage: BroodStage| {
+        larva_q
+            .iter()
+            .filter(|(larva, _)| larva.stage() == stage)
+            .count()
+    };
+    let starving = larva_q
+        .iter()
+        .filter(|(larva, paused)| !paused && larva.is_starving())
+        .count();
+    let queen = match queen_q.get_single() {
+        Ok(queen) if queen.is_starving() => format!("Queen: {:.0} hp, starving!", queen.health()),
+        Ok(queen) => format!(
+            "Queen: {:.0} hp, {:?} eggs ready",
+            queen.health(),
+            queen.eggs_ready()
+        ),
+        Err(_) => "Queen: dead".into(),
+    };
+    for mut text in header.iter_mut() {
+        text.sections[0].value = format!(
+            "{}\nBrood: {:?} eggs, {:?} larva, {:?} pupae\n{:?} starving, {:?} paused, {:?} awaiting eggs",
+            queen,
+            count(BroodStage::Egg),
+            count(BroodStage::Larva),
+            count(BroodStage::Pupa),
+            starving,
+            report.paused,
+            report.awaiting_eggs
+        );
+    }
+}
+
+fn stage_color(stage: BroodStage) -> Color {
+    match stage {
+        BroodStage::Egg => Color::WHITE,
+        BroodStage::Larva => Color::rgb_u8(238, 195, 154),
+        BroodStage::Pupa => Color::rgb_u8(143, 86, 59),
+    }
+}
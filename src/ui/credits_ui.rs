@@ -2,7 +2,7 @@ use bevy::{prelude::*, ui::RelativeCursorPosition};
 
 use super::{
     menu_ui::UIAnchorNode,
-    ui_util::{px, ProjectLocalStyle, UICommandsExt, ALL, LARGE, MEDIUM},
+    ui_util::{px, Focusable, ProjectLocalStyle, UICommandsExt, ALL, LARGE, MEDIUM},
 };
 use bevy_nine_slice_ui::{NineSliceUiMaterialBundle, NineSliceUiTexture};
 use leafwing_input_manager::{
@@ -173,6 +173,7 @@ fn instantiate_credits_ui(
                 ..default()
             },
             Interaction::None,
+            Focusable(UIFocus::Credits),
         ))
         .insert(ActionStateDriver {
             action: CreditsUIActions::ExitCredits,
@@ -208,6 +209,7 @@ fn instantiate_credits_ui(
                     CreditsUIActions::ScrollCredits,
                 )
                 .insert(KeyCode::Escape, CreditsUIActions::ExitCredits)
+                .insert(GamepadButtonType::East, CreditsUIActions::ExitCredits)
                 .build(),
             ..default()
         })
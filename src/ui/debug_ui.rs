@@ -0,0 +1,142 @@
+use bevy::prelude::*;
+use bevy_nine_slice_ui::*;
+use leafwing_input_manager::prelude::ActionState;
+
+use super::{gamefield_ui::GamefieldUIRoot, ui_util::*};
+use crate::{gizmodable::DebugGizmosOn, playerinput::GamefieldActions, UIFocus};
+
+pub struct DebugMenuPlugin;
+
+impl Plugin for DebugMenuPlugin {
+    fn build(&self, app: &mut App) {
+        app.add_systems(
+            Update,
+            (
+                spawn_debug_menu,
+                (toggle_debug_menu, toggle_debug_layer).run_if(in_state(UIFocus::Gamefield)),
+                debug_menu_display,
+            )
+                .chain(),
+        );
+    }
+}
+
+#[derive(Component)]
+struct DebugMenu;
+// One row per registered layer, plus one for gizmos as a whole.
+#[derive(Component)]
+enum DebugMenuRow {
+    AllGizmos,
+    Layer(String),
+}
+
+fn spawn_debug_menu(
+    mut commands: Commands,
+    asset_server: Res<AssetServer>,
+    gizmos: Res<DebugGizmosOn>,
+    root: Query<Entity, Added<GamefieldUIRoot>>,
+) {
+    let Ok(root) = root.get_single() else {
+        return;
+    };
+    let menu = commands
+        .spawn((
+            NineSliceUiMaterialBundle {
+                style: Style {
+                    display: Display::None,
+                    position_type: PositionType::Absolute,
+                    left: Val::Percent(50.),
+                    top: px(8.),
+                    margin: UiRect::left(px(-80.)),
+                    width: px(160.),
+                    padding: UiRect::all(px(6.)),
+                    flex_direction: FlexDirection::Column,
+                    row_gap: px(2.),
+                    ..default()
+                },
+                nine_slice_texture: NineSliceUiTexture::from_image(
+                    asset_server.load("nine_slice/bgW_bG_soft_corner_2.png"),
+                ),
+                z_index: ZIndex::Global(60),
+                ..default()
+            },
+            Interaction::None,
+            DebugMenu,
+            Name::new("Debug Menu"),
+        ))
+        .id();
+    let title = commands
+        .make_text("Debug [F3]", TextStyle::local(MEDIUM, Color::BLACK))
+        .id();
+    commands.entity(root).add_child(menu);
+    commands.entity(menu).add_child(title);
+
+    let rows = std::iter::once(DebugMenuRow::AllGizmos)
+        .chain(
+            gizmos
+                .systems()
+                .into_iter()
+                .map(|(layer, _)| DebugMenuRow::Layer(layer)),
+        )
+        .map(|row| {
+            commands
+                .make_text("", TextStyle::local(SMALL, Color::BLACK))
+                .insert((Interaction::None, Focusable(UIFocus::Gamefield), row))
+                .id()
+        })
+        .collect::<Vec<Entity>>();
+    commands.entity(menu).push_children(&rows);
+}
+
+fn toggle_debug_menu(
+    actions: Query<&ActionState<GamefieldActions>>,
+    mut menu: Query<&mut Style, With<DebugMenu>>,
+) {
+    if !actions
+        .iter()
+        .any(|action| action.just_pressed(GamefieldActions::ToggleDebugMenu))
+    {
+        return;
+    }
+    for mut style in menu.iter_mut() {
+        style.display = match style.display {
+            Display::None => Display::Flex,
+            _ => Display::None,
+        };
+    }
+}
+
+fn toggle_debug_layer(
+    mut gizmos: ResMut<DebugGizmosOn>,
+    rows: Query<(&Interaction, &DebugMenuRow), Changed<Interaction>>,
+) {
+    for (interaction, row) in rows.iter() {
+        if !matches!(interaction, Interaction::Pressed) {
+            continue;
+        }
+        match row {
+            DebugMenuRow::AllGizmos => gizmos.toggle_render(),
+            DebugMenuRow::Layer(layer) => gizmos.toggle_system(layer),
+        }
+    }
+}
+
+fn debug_menu_display(
+    gizmos: Res<DebugGizmosOn>,
+    mut rows: Query<(&mut Text, &DebugMenuRow, &Interaction)>,
+) {
+    for (mut text, row, interaction) in rows.iter_mut() {
+        let (label, enabled) = match row {
+            DebugMenuRow::AllGizmos => ("all gizmos", gizmos.render_enabled()),
+            DebugMenuRow::Layer(layer) => (layer.as_str(), gizmos.layer_enabled(layer)),
+        };
+        let check = if enabled { "x" } else { " " };
+        text.sections[0].value = format!("[{}] {}", check, label);
+        // Layers only draw while gizmos as a whole are on, so grey them out otherwise.
+        text.sections[0].style.color = match (row, interaction) {
+            (_, Interaction::Hovered) => PURPLE(),
+            (DebugMenuRow::Layer(_), _) if !gizmos.render_enabled() => Color::GRAY,
+            _ => Color::BLACK,
+        };
+    }
+}
@@ -1,15 +1,20 @@
 use std::marker::PhantomData;
 
 use bevy::prelude::*;
-use leafwing_input_manager::plugin::ToggleActions;
+use leafwing_input_manager::{
+    action_state::{ActionState, ActionStateDriver},
+    plugin::ToggleActions,
+    InputManagerBundle,
+};
 
 use super::{menu_ui::UIAnchorNode, ui_util::*, upgrades::spawn_upgrade_buttons};
 use crate::{
     ant::{ForagerAnt, IdleAnt, NursemaidAnt},
+    calendar::SimCalendar,
     colony::{AntCapacity, AntPopulation, Colony, LaborData, LaborPhase, LarvaTarget, MaxFood},
-    food::FoodQuant,
+    food::{FoodProduction, FoodQuant},
     playerinput::{CameraControl, GamefieldActions},
-    UIFocus,
+    SimState, UIFocus,
 };
 use bevy_nine_slice_ui::*;
 
@@ -19,6 +24,7 @@ impl Plugin for GamefieldUI {
     fn build(&self, app: &mut App) {
         app.add_systems(OnEnter(UIFocus::Gamefield), activate_gamefield_actions)
             .add_systems(OnExit(UIFocus::Gamefield), deactivate_gamefield_actions)
+            .add_systems(OnEnter(SimState::GameOver), spawn_game_over_panel)
             .add_systems(
                 Update,
                 init_gamefield_ui.run_if(in_state(UIFocus::Gamefield).and_then(run_once())),
@@ -26,7 +32,7 @@ impl Plugin for GamefieldUI {
             .add_systems(
                 Update,
                 (
-                    (food_text_update, ant_text_update),
+                    (food_text_update, ant_text_update, calendar_display),
                     (
                         ant_bar_update::<ForagerAnt>,
                         ant_bar_update::<NursemaidAnt>,
@@ -113,13 +119,10 @@ impl LaborTableDisplay for IdleAnt {
     }
 }
 
-#[derive(Component)]
-struct LarvaPlus;
-#[derive(Component)]
-struct LarvaMinus;
-
 #[derive(Component)]
 struct TargetLarvaDisplay;
+#[derive(Component)]
+struct CalendarDisplay;
 
 fn activate_gamefield_actions(
     mut gamefield_actions: ResMut<ToggleActions<GamefieldActions>>,
@@ -154,6 +157,8 @@ fn init_gamefield_ui(
             },
             Name::new("Gamefield UI Root"),
             GamefieldUIRoot,
+            // Unbound - the buttons drive it so clicking and hotkeys end up as the same actions.
+            InputManagerBundle::<GamefieldActions>::default(),
         ))
         .id();
 
@@ -387,7 +392,11 @@ fn init_gamefield_ui(
                 ..default()
             },
             Interaction::None,
-            LarvaPlus,
+            Focusable(UIFocus::Gamefield),
+            ActionStateDriver {
+                action: GamefieldActions::LarvaUp,
+                targets: root.into(),
+            },
         ))
         .id();
     let egg_plus_button_icon = commands.make_icon("green_plus.png".into());
@@ -412,7 +421,11 @@ fn init_gamefield_ui(
                 ..default()
             },
             Interaction::None,
-            LarvaMinus,
+            Focusable(UIFocus::Gamefield),
+            ActionStateDriver {
+                action: GamefieldActions::LarvaDown,
+                targets: root.into(),
+            },
         ))
         .id();
     let egg_minus_button_icon = commands.make_icon("red_minus.png".into());
@@ -498,7 +511,31 @@ fn init_gamefield_ui(
         })
         .id();
 
-    let upgrade_buttons = spawn_upgrade_buttons(&mut commands, &asset_server);
+    let calendar_layout = commands
+        .spawn(NineSliceUiMaterialBundle {
+            style: Style {
+                position_type: PositionType::Absolute,
+                left: px(8.),
+                top: px(8.),
+                padding: UiRect::axes(px(10.), px(6.)),
+                ..default()
+            },
+            nine_slice_texture: NineSliceUiTexture::from_image(
+                asset_server.load("nine_slice/bgW_bG_soft_corner_2.png"),
+            ),
+            ..default()
+        })
+        .id();
+    let calendar_text = commands
+        .make_text_sections(vec![
+            ("", TextStyle::local(MEDIUM, Color::BLACK)),
+            ("\n", TextStyle::local(MEDIUM, Color::BLACK)),
+            ("", TextStyle::local(MEDIUM, Color::BLACK)),
+        ])
+        .insert(CalendarDisplay)
+        .id();
+
+    let upgrade_buttons = spawn_upgrade_buttons(&mut commands, &asset_server, root);
     let menu_children = [upgrade_buttons.as_slice()].concat();
     commands.entity(anchor.0).add_child(root);
     commands.entity(root).add_child(big_bar_layout);
@@ -526,6 +563,9 @@ fn init_gamefield_ui(
         .entity(ant_labor_table)
         .push_children(&[forager_row, nursemaid_row, idler_row]);
 
+    commands.entity(root).add_child(calendar_layout);
+    commands.entity(calendar_layout).add_child(calendar_text);
+
     commands.entity(root).add_child(upgrade_menu_layout);
     commands
         .entity(upgrade_menu_layout)
@@ -798,12 +838,16 @@ fn food_text_update(
     mut food_text: Query<&mut Text, With<GamefieldUIFoodBar>>,
     mut style_q: Query<&mut Style, (With<GamefieldUIFoodBar>, Without<Text>)>,
     q_col: Query<(&FoodQuant, &MaxFood), With<Colony>>,
+    production: Res<FoodProduction>,
 ) {
     if let Ok((food, maxfood)) = q_col.get_single() {
         for mut text in food_text.iter_mut() {
             text.sections[0].value = format!("Food: {:?} ", food.0);
             text.sections[1].value = "/".into();
-            text.sections[2].value = format!(" {:?}", maxfood.0);
+            text.sections[2].value = format!(
+                " {:?}   World: +{:.0}/min",
+                maxfood.0, production.per_minute
+            );
         }
         for mut style in style_q.iter_mut() {
             style.width = Val::Percent(100. * (food.0 as f32 / maxfood.0 as f32));
@@ -844,6 +888,68 @@ fn ant_text_update(
     }
 }
 
+fn spawn_game_over_panel(
+    mut commands: Commands,
+    asset_server: Res<AssetServer>,
+    root: Query<Entity, With<GamefieldUIRoot>>,
+) {
+    let Ok(root) = root.get_single() else {
+        return;
+    };
+    let panel = commands
+        .spawn((
+            NineSliceUiMaterialBundle {
+                style: Style {
+                    position_type: PositionType::Absolute,
+                    left: Val::Percent(50.),
+                    top: Val::Percent(40.),
+                    margin: UiRect::left(px(-130.)),
+                    width: px(260.),
+                    padding: UiRect::all(px(12.)),
+                    flex_direction: FlexDirection::Column,
+                    align_items: AlignItems::Center,
+                    row_gap: px(6.),
+                    ..default()
+                },
+                nine_slice_texture: NineSliceUiTexture::from_image(
+                    asset_server.load("nine_slice/bgW_bG_soft_corner_2.png"),
+                ),
+                z_index: ZIndex::Global(100),
+                ..default()
+            },
+            Name::new("Game Over"),
+        ))
+        .id();
+    let title = commands
+        .make_text("The Queen is dead", TextStyle::local(LARGE, RED()))
+        .id();
+    let subtitle = commands
+        .make_text(
+            "With no one to lay eggs, your colony is lost.",
+            TextStyle::local(MEDIUM, Color::BLACK),
+        )
+        .id();
+    commands.entity(root).add_child(panel);
+    commands.entity(panel).push_children(&[title, subtitle]);
+}
+
+fn calendar_display(
+    calendar: Res<SimCalendar>,
+    mut text_q: Query<&mut Text, With<CalendarDisplay>>,
+) {
+    let (hour, minute) = calendar.clock_time();
+    let light = if calendar.is_night() { "Night" } else { "Day" };
+    for mut text in text_q.iter_mut() {
+        text.sections[0].value = format!(
+            "{:?} {:?} - Day {:?}",
+            calendar.season(),
+            calendar.day_of_season(),
+            calendar.day()
+        );
+        text.sections[2].value = format!("{:02}:{:02} {}", hour, minute, light);
+    }
+}
+
 fn larva_target_display(
     mut larva_text: Query<&mut Text, With<TargetLarvaDisplay>>,
     q_col: Query<&LarvaTarget, With<Colony>>,
@@ -854,39 +960,32 @@ fn larva_target_display(
     }
 }
 
+// Both the hotkey and the button count - holding either one keeps adjusting the target.
 fn increment_target_larva(
-    mut chill: Local<CoolDown>,
+    mut repeat: Local<HoldToRepeat>,
     time: Res<Time>,
     mut q_col: Query<&mut LarvaTarget, With<Colony>>,
-    interaction: Query<&Interaction, (With<LarvaPlus>, Without<LarvaMinus>)>,
+    actions: Query<&ActionState<GamefieldActions>>,
 ) {
-    chill.handle_time(time.delta());
-    if !chill.cooling_down() {
-        interaction.iter().take(1).for_each(|n| match *n {
-            Interaction::Pressed => {
-                q_col.single_mut().0 += 1;
-                chill.start_cooldown();
-            }
-            _ => {}
-        });
+    let held = actions
+        .iter()
+        .any(|action| action.pressed(GamefieldActions::LarvaUp));
+    if repeat.tick(held, time.delta()) {
+        q_col.single_mut().0 += 1;
     }
 }
 
 fn decrement_target_larva(
-    mut chill: Local<CoolDown>,
+    mut repeat: Local<HoldToRepeat>,
     time: Res<Time>,
     mut q_col: Query<&mut LarvaTarget, With<Colony>>,
-    interaction: Query<&Interaction, (With<LarvaMinus>, Without<LarvaPlus>)>,
+    actions: Query<&ActionState<GamefieldActions>>,
 ) {
-    chill.handle_time(time.delta());
-    if !chill.cooling_down() {
-        interaction.iter().take(1).for_each(|n| match *n {
-            Interaction::Pressed => {
-                let mut larva_target = q_col.single_mut();
-                larva_target.0 = 1.max(larva_target.0 - 1);
-                chill.start_cooldown();
-            }
-            _ => {}
-        });
+    let held = actions
+        .iter()
+        .any(|action| action.pressed(GamefieldActions::LarvaDown));
+    if repeat.tick(held, time.delta()) {
+        let mut larva_target = q_col.single_mut();
+        larva_target.0 = 1.max(larva_target.0 - 1);
     }
 }
@@ -0,0 +1,257 @@
+use bevy::prelude::*;
+use bevy_nine_slice_ui::*;
+use bevy_spatial::{kdtree::KDTree2, SpatialAccess};
+use leafwing_input_manager::prelude::ActionState;
+
+use super::{gamefield_ui::GamefieldUIRoot, ui_util::*};
+use crate::{
+    ant::{Ant, Carried, Drift, ForagerAnt, Navigate, NursemaidAnt, Starving},
+    calendar::SimCalendar,
+    food::FoodQuant,
+    gametimer::SimTimer,
+    playerinput::{CursorWorldPos, GamefieldActions, LongPress},
+    AntSpatialMarker, UIFocus,
+};
+
+pub struct AntInspectorPlugin;
+
+impl Plugin for AntInspectorPlugin {
+    fn build(&self, app: &mut App) {
+        app.init_resource::<InspectedAnt>().add_systems(
+            Update,
+            (
+                spawn_ant_inspector,
+                (pick_ant, toggle_follow).run_if(in_state(UIFocus::Gamefield)),
+                update_ant_inspector,
+                highlight_inspected_ant,
+            )
+                .chain(),
+        );
+    }
+}
+
+// How close, in world units, a click or long press has to land to an ant to pick it.
+pub const PICK_RADIUS: f32 = 6.0;
+const HIGHLIGHT_RADIUS: f32 = 5.0;
+
+// The ant the player has clicked on, if any, and whether the camera is following it around.
+#[derive(Resource, Default)]
+pub struct InspectedAnt {
+    ant: Option<Entity>,
+    follow: bool,
+}
+impl InspectedAnt {
+    pub fn followed(&self) -> Option<Entity> {
+        self.ant.filter(|_| self.follow)
+    }
+    pub fn stop_following(&mut self) {
+        self.follow = false;
+    }
+}
+
+#[derive(Component)]
+struct AntInspectorPanel;
+#[derive(Component)]
+struct AntInspectorText;
+#[derive(Component)]
+struct FollowButton;
+
+type InspectorQuery<'w, 's> = Query<
+    'w,
+    's,
+    (
+        &'static Ant,
+        Option<&'static ForagerAnt>,
+        Has<NursemaidAnt>,
+        &'static SimTimer,
+        &'static Navigate,
+        &'static Drift,
+        Has<Starving>,
+        Option<&'static Children>,
+    ),
+>;
+
+fn spawn_ant_inspector(
+    mut commands: Commands,
+    asset_server: Res<AssetServer>,
+    root: Query<Entity, Added<GamefieldUIRoot>>,
+) {
+    let Ok(root) = root.get_single() else {
+        return;
+    };
+    let panel = commands
+        .spawn((
+            NineSliceUiMaterialBundle {
+                style: Style {
+                    display: Display::None,
+                    position_type: PositionType::Absolute,
+                    left: px(8.),
+                    top: px(96.),
+                    width: px(200.),
+                    padding: UiRect::all(px(6.)),
+                    flex_direction: FlexDirection::Column,
+                    row_gap: px(4.),
+                    ..default()
+                },
+                nine_slice_texture: NineSliceUiTexture::from_image(
+                    asset_server.load("nine_slice/bgW_bG_soft_corner_2.png"),
+                ),
+                ..default()
+            },
+            // So that clicks on the panel don't fall through to the gamefield and deselect the ant.
+            Interaction::None,
+            AntInspectorPanel,
+            Name::new("Ant Inspector"),
+        ))
+        .id();
+    let text = commands
+        .make_text("", TextStyle::local(SMALL, Color::BLACK))
+        .insert(AntInspectorText)
+        .id();
+    let follow_button = commands
+        .spawn((
+            NineSliceUiMaterialBundle {
+                style: Style {
+                    padding: UiRect::axes(px(8.), px(3.)),
+                    align_self: AlignSelf::Start,
+                    ..default()
+                },
+                nine_slice_texture: NineSliceUiTexture::from_image(
+                    asset_server.load("nine_slice/upgrade_card_container_backdrop.png"),
+                ),
+                ..default()
+            },
+            Interaction::None,
+            Focusable(UIFocus::Gamefield),
+            FollowButton,
+        ))
+        .id();
+    let follow_text = commands
+        .make_text("Follow [F]", TextStyle::local(SMALL, Color::BLACK))
+        .id();
+    commands.entity(root).add_child(panel);
+    commands.entity(panel).push_children(&[text, follow_button]);
+    commands.entity(follow_button).add_child(follow_text);
+}
+
+fn pick_ant(
+    actions: Query<&ActionState<GamefieldActions>>,
+    cursor: CursorWorldPos,
+    mut long_presses: EventReader<LongPress>,
+    ants: Res<KDTree2<AntSpatialMarker>>,
+    ui: Query<&Interaction>,
+    mut inspected: ResMut<InspectedAnt>,
+) {
+    let clicked = actions
+        .iter()
+        .any(|action| action.just_pressed(GamefieldActions::GameFieldClick))
+        // Clicks on the UI aren't meant for the gamefield.
+        && !ui
+            .iter()
+            .any(|interaction| !matches!(interaction, Interaction::None));
+    let long_pressed = long_presses.read().last().map(|press| press.0);
+    let Some(pos) = long_pressed.or_else(|| cursor.get().filter(|_| clicked)) else {
+        return;
+    };
+    let picked = ants
+        .nearest_neighbour(pos)
+        .filter(|(ant_pos, _)| ant_pos.distance(pos) <= PICK_RADIUS)
+        .and_then(|(_, ant)| ant);
+    if picked != inspected.ant {
+        inspected.ant = picked;
+        inspected.follow = false;
+    }
+}
+
+fn toggle_follow(
+    actions: Query<&ActionState<GamefieldActions>>,
+    button: Query<&Interaction, (Changed<Interaction>, With<FollowButton>)>,
+    mut inspected: ResMut<InspectedAnt>,
+) {
+    let pressed = actions
+        .iter()
+        .any(|action| action.just_pressed(GamefieldActions::FollowAnt))
+        || button
+            .iter()
+            .any(|interaction| matches!(interaction, Interaction::Pressed));
+    if pressed && inspected.ant.is_some() {
+        inspected.follow = !inspected.follow;
+    }
+}
+
+fn update_ant_inspector(
+    mut inspected: ResMut<InspectedAnt>,
+    calendar: Res<SimCalendar>,
+    ant_q: InspectorQuery,
+    carried_q: Query<&FoodQuant, With<Carried>>,
+    mut panel: Query<&mut Style, With<AntInspectorPanel>>,
+    mut text_q: Query<&mut Text, With<AntInspectorText>>,
+) {
+    let Ok(mut style) = panel.get_single_mut() else {
+        return;
+    };
+    let Some((entity, (ant, forager, nursemaid, timer, nav, drift, starving, children))) =
+        inspected
+            .ant
+            .and_then(|entity| ant_q.get(entity).ok().map(|ant| (entity, ant)))
+    else {
+        // Nothing picked, or the ant we were looking at has died.
+        if inspected.ant.is_some() {
+            inspected.ant = None;
+            inspected.follow = false;
+        }
+        if style.display != Display::None {
+            style.display = Display::None;
+        }
+        return;
+    };
+    if style.display != Display::Flex {
+        style.display = Display::Flex;
+    }
+
+    let role = match (forager, nursemaid) {
+        (Some(forager), _) => format!("Forager - {}", forager.describe()),
+        (None, true) => "Nursemaid".into(),
+        (None, false) => "Idle".into(),
+    };
+    let carrying: i32 = children
+        .map(|children| carried_q.iter_many(children).map(|food| food.0).sum())
+        .unwrap_or(0);
+    let age = ant.age(&calendar).as_secs();
+    let heading = nav.move_to().map_or("nowhere".into(), |dest| {
+        format!("({:.0}, {:.0})", dest.x, dest.y)
+    });
+    let drift = drift.velocity();
+    let mut lines = vec![
+        format!("Ant {:?}", entity),
+        format!("Role: {}", role),
+        format!("Carrying: {:?} food", carrying),
+        format!("Age: {}m {:02}s", age / 60, age % 60),
+        format!("Timer: {:.1}s left", timer.time.remaining_secs()),
+        format!("Heading to: {}", heading),
+        format!("Drift: ({:.2}, {:.2})", drift.x, drift.y),
+    ];
+    if starving {
+        lines.push("Starving!".into());
+    }
+    if inspected.follow {
+        lines.push("Following with the camera".into());
+    }
+    for mut text in text_q.iter_mut() {
+        text.sections[0].value = lines.join("\n");
+    }
+}
+
+fn highlight_inspected_ant(
+    mut gizmos: Gizmos,
+    inspected: Res<InspectedAnt>,
+    ants: Query<&GlobalTransform, With<Ant>>,
+) {
+    if let Some(transform) = inspected.ant.and_then(|ant| ants.get(ant).ok()) {
+        gizmos.circle_2d(
+            transform.translation().truncate(),
+            HIGHLIGHT_RADIUS,
+            Color::YELLOW,
+        );
+    }
+}
@@ -1,14 +1,17 @@
 use bevy::app::AppExit;
 use bevy::prelude::*;
 use bevy_nine_slice_ui::{NineSliceUiMaterialBundle, NineSliceUiTexture};
+use bevy_prng::WyRand;
+use bevy_rand::resource::GlobalEntropy;
 use leafwing_input_manager::{
     action_state::{ActionState, ActionStateDriver},
     plugin::{InputManagerPlugin, ToggleActions},
     Actionlike, InputManagerBundle,
 };
+use rand::Rng;
 
-use super::ui_util::ProjectLocalStyle;
-use crate::{GameStarted, UIFocus};
+use super::ui_util::{Focusable, ProjectLocalStyle, MEDIUM};
+use crate::{worldgen::WorldGenSettings, GameStarted, UIFocus};
 
 pub struct MainMenuUI;
 
@@ -46,6 +49,9 @@ impl Plugin for MainMenuUI {
                     toggle_settings.in_set(InputHandlers::ButtonClick),
                     credits_button_onclick.in_set(InputHandlers::ButtonClick),
                     start_button_onclick.in_set(InputHandlers::ButtonClick),
+                    (world_setup_onclick, world_setup_labels)
+                        .chain()
+                        .in_set(InputHandlers::ButtonClick),
                 ),
             );
     }
@@ -59,8 +65,15 @@ pub enum MainMenuUIActions {
     ExitGame,
     OpenSettings,
     OpenCredits,
+    RerollWorldSeed,
+    CycleWorldTerrain,
+    CycleWorldFood,
+    ToggleWorldBoundary,
 }
 
+#[derive(Component)]
+struct WorldSetupLabel(MainMenuUIActions);
+
 fn open_menu_on_start(mut ui_focus: ResMut<NextState<UIFocus>>) {
     ui_focus.set(UIFocus::MainMenu);
 }
@@ -174,6 +187,43 @@ fn display_main_menu(
         credits_button,
         quit_button,
     ]);
+
+    // World generation options only matter until the world has been generated.
+    if game_start.is_none() {
+        let world_setup_layout_node = commands
+            .spawn(NodeBundle {
+                style: Style {
+                    width: Val::Px(157.0),
+                    height: Val::Percent(100.0),
+                    align_items: AlignItems::Center,
+                    justify_content: JustifyContent::Center,
+                    flex_direction: FlexDirection::Column,
+                    ..default()
+                },
+                ..default()
+            })
+            .id();
+        let world_setup_buttons = [
+            MainMenuUIActions::RerollWorldSeed,
+            MainMenuUIActions::CycleWorldTerrain,
+            MainMenuUIActions::CycleWorldFood,
+            MainMenuUIActions::ToggleWorldBoundary,
+        ]
+        .map(|action| {
+            world_setup_button(
+                &mut commands,
+                button_texture.clone(),
+                ActionStateDriver {
+                    action,
+                    targets: root_node.into(),
+                },
+            )
+        });
+        commands.entity(root_node).add_child(world_setup_layout_node);
+        commands
+            .entity(world_setup_layout_node)
+            .push_children(&world_setup_buttons);
+    }
 }
 
 fn start_button_onclick(
@@ -198,6 +248,44 @@ fn credits_button_onclick(
     }
 }
 
+fn world_setup_onclick(
+    q: Query<&ActionState<MainMenuUIActions>>,
+    mut settings: ResMut<WorldGenSettings>,
+    mut rng: ResMut<GlobalEntropy<WyRand>>,
+) {
+    for n in q.iter() {
+        if n.just_pressed(MainMenuUIActions::RerollWorldSeed) {
+            settings.seed = rng.gen();
+        }
+        if n.just_pressed(MainMenuUIActions::CycleWorldTerrain) {
+            settings.terrain = settings.terrain.next();
+        }
+        if n.just_pressed(MainMenuUIActions::CycleWorldFood) {
+            settings.food = settings.food.next();
+        }
+        if n.just_pressed(MainMenuUIActions::ToggleWorldBoundary) {
+            settings.bounded = !settings.bounded;
+        }
+    }
+}
+
+fn world_setup_labels(
+    settings: Res<WorldGenSettings>,
+    mut q: Query<(&mut Text, &WorldSetupLabel)>,
+) {
+    for (mut text, label) in q.iter_mut() {
+        text.sections[0].value = match label.0 {
+            MainMenuUIActions::RerollWorldSeed => format!("Seed: {:?}", settings.seed),
+            MainMenuUIActions::CycleWorldTerrain => format!("Terrain: {:?}", settings.terrain),
+            MainMenuUIActions::CycleWorldFood => format!("Food: {:?}", settings.food),
+            MainMenuUIActions::ToggleWorldBoundary => {
+                format!("Boundary: {}", if settings.bounded { "On" } else { "Off" })
+            }
+            _ => continue,
+        };
+    }
+}
+
 fn quit_button_onclick(
     q: Query<&ActionState<MainMenuUIActions>>,
     mut exit_event: EventWriter<AppExit>,
@@ -236,6 +324,7 @@ fn main_menu_button(
                 ..default()
             },
             Interaction::None,
+            Focusable(UIFocus::MainMenu),
             Name::new(format!("MainMenu: {:?} Button", button_text.clone())),
         ))
         .insert(action_driver)
@@ -249,6 +338,37 @@ fn main_menu_button(
     commands.entity(button).add_child(button_label);
     button
 }
+fn world_setup_button(
+    commands: &mut Commands,
+    image: Handle<Image>,
+    action_driver: ActionStateDriver<MainMenuUIActions>,
+) -> Entity {
+    let action = action_driver.action;
+    let button = commands
+        .spawn((
+            NineSliceUiMaterialBundle {
+                style: main_menu_button_style(),
+                nine_slice_texture: NineSliceUiTexture::from_image(image),
+                ..default()
+            },
+            Interaction::None,
+            Focusable(UIFocus::MainMenu),
+            Name::new(format!("MainMenu: {:?} Button", action)),
+        ))
+        .insert(action_driver)
+        .id();
+    let button_label = commands
+        .spawn((
+            TextBundle {
+                text: Text::from_section("", TextStyle::local(MEDIUM, Color::BLACK)),
+                ..default()
+            },
+            WorldSetupLabel(action),
+        ))
+        .id();
+    commands.entity(button).add_child(button_label);
+    button
+}
 #[cfg(not(target_arch = "wasm32"))]
 fn quit_button(commands: &mut Commands, image: Handle<Image>, action_driver: ActionStateDriver<MainMenuUIActions>) -> Entity {
    main_menu_button(commands, image, action_driver, "Quit")
@@ -0,0 +1,308 @@
+use std::time::Duration;
+
+use bevy::{prelude::*, time::common_conditions::on_timer, ui::RelativeCursorPosition};
+use bevy_nine_slice_ui::*;
+use bevy_spatial::{kdtree::KDTree2, SpatialAccess};
+
+use super::{gamefield_ui::GamefieldUIRoot, inspector_ui::InspectedAnt, ui_util::*};
+use crate::{
+    ant::Ant,
+    colony::{Colony, ColonyPos},
+    playerinput::CameraMotion,
+    worldgen::WorldBounds,
+    MainCamera, SpatialMarker, UIFocus,
+};
+
+pub struct MinimapPlugin;
+
+impl Plugin for MinimapPlugin {
+    fn build(&self, app: &mut App) {
+        app.add_systems(
+            Update,
+            (
+                spawn_minimap,
+                (show_ant_density, show_food, show_nest)
+                    .run_if(on_timer(Duration::from_secs_f32(MINIMAP_REFRESH_SECS))),
+                jump_camera_to_minimap.run_if(in_state(UIFocus::Gamefield)),
+                show_camera_viewport,
+            )
+                .chain(),
+        );
+    }
+}
+
+const MINIMAP_WIDTH: f32 = 150.0;
+// The food KD-tree only updates twice a second anyway.
+const MINIMAP_REFRESH_SECS: f32 = 0.5;
+// The ant density overlay is a grid this many cells on a side.
+const DENSITY_CELLS: usize = 20;
+const FOOD_DOT_SIZE: f32 = 3.0;
+const NEST_DOT_SIZE: f32 = 6.0;
+
+#[derive(Component)]
+struct MinimapField;
+#[derive(Component)]
+struct DensityCell {
+    column: usize,
+    row: usize,
+}
+#[derive(Component)]
+struct FoodDot;
+#[derive(Component)]
+struct NestDot;
+#[derive(Component)]
+struct ViewportFrame;
+
+// Where a world position sits on the minimap, as percentages from its top left corner.
+fn minimap_percent(bounds: &Rect, pos: Vec2) -> Vec2 {
+    Vec2::new(
+        (pos.x - bounds.min.x) / bounds.width(),
+        (bounds.max.y - pos.y) / bounds.height(),
+    ) * 100.
+}
+
+fn place_dot(style: &mut Style, bounds: &Rect, pos: Vec2) {
+    let at = minimap_percent(bounds, pos);
+    style.left = Val::Percent(at.x);
+    style.top = Val::Percent(at.y);
+}
+
+fn dot_style(size: f32) -> Style {
+    Style {
+        position_type: PositionType::Absolute,
+        width: px(size),
+        height: px(size),
+        // Centers the dot on its position rather than hanging it off the corner.
+        margin: UiRect::all(px(-size / 2.)),
+        ..default()
+    }
+}
+
+// The whole world shrunk down into the bottom right corner of the gamefield UI - click it to send the camera there.
+fn spawn_minimap(
+    mut commands: Commands,
+    asset_server: Res<AssetServer>,
+    bounds: Res<WorldBounds>,
+    root: Query<Entity, Added<GamefieldUIRoot>>,
+) {
+    let Ok(root) = root.get_single() else {
+        return;
+    };
+    let panel = commands
+        .spawn((
+            NineSliceUiMaterialBundle {
+                style: Style {
+                    position_type: PositionType::Absolute,
+                    right: px(8.),
+                    bottom: px(8.),
+                    padding: UiRect::all(px(6.)),
+                    ..default()
+                },
+                nine_slice_texture: NineSliceUiTexture::from_image(
+                    asset_server.load("nine_slice/bgW_bG_soft_corner_2.png"),
+                ),
+                ..default()
+            },
+            Name::new("Minimap"),
+        ))
+        .id();
+    let field = commands
+        .spawn((
+            NodeBundle {
+                style: Style {
+                    width: px(MINIMAP_WIDTH),
+                    height: px(MINIMAP_WIDTH * bounds.0.height() / bounds.0.width()),
+                    overflow: Overflow::clip(),
+                    ..default()
+                },
+                background_color: Color::rgb_u8(89, 86, 82).into(),
+                ..default()
+            },
+            MinimapField,
+            Interaction::None,
+            RelativeCursorPosition::default(),
+        ))
+        .id();
+    let cell_size = 100. / DENSITY_CELLS as f32;
+    let cells = (0..DENSITY_CELLS * DENSITY_CELLS)
+        .map(|i| {
+            let (column, row) = (i % DENSITY_CELLS, i / DENSITY_CELLS);
+            commands
+                .spawn((
+                    NodeBundle {
+                        style: Style {
+                            position_type: PositionType::Absolute,
+                            left: Val::Percent(column as f32 * cell_size),
+                            top: Val::Percent(row as f32 * cell_size),
+                            width: Val::Percent(cell_size),
+                            height: Val::Percent(cell_size),
+                            ..default()
+                        },
+                        background_color: Color::NONE.into(),
+                        ..default()
+                    },
+                    DensityCell { column, row },
+                ))
+                .id()
+        })
+        .collect::<Vec<Entity>>();
+    let nest = commands
+        .spawn((
+            NodeBundle {
+                style: Style {
+                    display: Display::None,
+                    ..dot_style(NEST_DOT_SIZE)
+                },
+                background_color: RED().into(),
+                z_index: ZIndex::Local(1),
+                ..default()
+            },
+            NestDot,
+        ))
+        .id();
+    let viewport = commands
+        .spawn((
+            NodeBundle {
+                style: Style {
+                    position_type: PositionType::Absolute,
+                    border: UiRect::all(px(1.)),
+                    ..default()
+                },
+                border_color: Color::WHITE.into(),
+                z_index: ZIndex::Local(2),
+                ..default()
+            },
+            ViewportFrame,
+        ))
+        .id();
+    commands.entity(root).add_child(panel);
+    commands.entity(panel).add_child(field);
+    commands.entity(field).push_children(&cells);
+    commands.entity(field).push_children(&[nest, viewport]);
+}
+
+// Darker where more ants are, relative to the busiest part of the map.
+fn show_ant_density(
+    bounds: Res<WorldBounds>,
+    ants: Query<&GlobalTransform, With<Ant>>,
+    mut cells: Query<(&DensityCell, &mut BackgroundColor)>,
+) {
+    let mut counts = vec![0; DENSITY_CELLS * DENSITY_CELLS];
+    for ant in ants.iter() {
+        let at = minimap_percent(&bounds.0, ant.translation().truncate()) / 100.;
+        if !(0.0..1.0).contains(&at.x) || !(0.0..1.0).contains(&at.y) {
+            continue;
+        }
+        let (column, row) = (
+            (at.x * DENSITY_CELLS as f32) as usize,
+            (at.y * DENSITY_CELLS as f32) as usize,
+        );
+        counts[row * DENSITY_CELLS + column] += 1;
+    }
+    let busiest = counts.iter().copied().max().unwrap_or(0).max(1) as f32;
+    for (cell, mut color) in cells.iter_mut() {
+        let count = counts[cell.row * DENSITY_CELLS + cell.column] as f32;
+        *color = Color::rgba(0., 0., 0., (count / busiest).sqrt() * 0.8).into();
+    }
+}
+
+// One dot per chunk of food in the KD-tree - dots get reused, and the spares are hidden.
+fn show_food(
+    mut commands: Commands,
+    bounds: Res<WorldBounds>,
+    food: Res<KDTree2<SpatialMarker>>,
+    field: Query<Entity, With<MinimapField>>,
+    mut dots: Query<&mut Style, With<FoodDot>>,
+) {
+    let Ok(field) = field.get_single() else {
+        return;
+    };
+    let reach = bounds.0.half_size().length();
+    let mut positions = food
+        .within_distance(bounds.0.center(), reach)
+        .into_iter()
+        .map(|(pos, _)| pos);
+    for mut style in dots.iter_mut() {
+        if let Some(pos) = positions.next() {
+            style.display = Display::Flex;
+            place_dot(&mut style, &bounds.0, pos);
+        } else {
+            style.display = Display::None;
+        }
+    }
+    for pos in positions {
+        let mut style = dot_style(FOOD_DOT_SIZE);
+        place_dot(&mut style, &bounds.0, pos);
+        let dot = commands
+            .spawn((
+                NodeBundle {
+                    style,
+                    background_color: GREEN().into(),
+                    ..default()
+                },
+                FoodDot,
+            ))
+            .id();
+        commands.entity(field).add_child(dot);
+    }
+}
+
+fn show_nest(
+    bounds: Res<WorldBounds>,
+    colony: Query<&ColonyPos, With<Colony>>,
+    mut nest: Query<&mut Style, With<NestDot>>,
+) {
+    let (Ok(home), Ok(mut style)) = (colony.get_single(), nest.get_single_mut()) else {
+        return;
+    };
+    style.display = Display::Flex;
+    place_dot(&mut style, &bounds.0, home.0);
+}
+
+fn jump_camera_to_minimap(
+    field: Query<(&Interaction, &RelativeCursorPosition), With<MinimapField>>,
+    bounds: Res<WorldBounds>,
+    mut camera: Query<(&mut Transform, &mut CameraMotion), With<MainCamera>>,
+    mut inspected: ResMut<InspectedAnt>,
+) {
+    let Ok((interaction, cursor)) = field.get_single() else {
+        return;
+    };
+    let Some(at) = cursor
+        .normalized
+        .filter(|_| *interaction == Interaction::Pressed)
+    else {
+        return;
+    };
+    let Ok((mut camera_transform, mut motion)) = camera.get_single_mut() else {
+        return;
+    };
+    let at = at.clamp(Vec2::ZERO, Vec2::ONE);
+    inspected.stop_following();
+    motion.stop();
+    camera_transform.translation.x = bounds.0.min.x + at.x * bounds.0.width();
+    camera_transform.translation.y = bounds.0.max.y - at.y * bounds.0.height();
+}
+
+// Outlines the part of the world the camera can currently see.
+fn show_camera_viewport(
+    bounds: Res<WorldBounds>,
+    camera: Query<(&Transform, &OrthographicProjection), With<MainCamera>>,
+    mut frame: Query<&mut Style, With<ViewportFrame>>,
+) {
+    let (Ok((camera_transform, projection)), Ok(mut style)) =
+        (camera.get_single(), frame.get_single_mut())
+    else {
+        return;
+    };
+    let seen = Rect::from_center_size(
+        camera_transform.translation.truncate(),
+        projection.area.size(),
+    )
+    .intersect(bounds.0);
+    let top_left = minimap_percent(&bounds.0, Vec2::new(seen.min.x, seen.max.y));
+    style.left = Val::Percent(top_left.x);
+    style.top = Val::Percent(top_left.y);
+    style.width = Val::Percent(seen.width() / bounds.0.width() * 100.);
+    style.height = Val::Percent(seen.height() / bounds.0.height() * 100.);
+}
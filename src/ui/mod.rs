@@ -1,13 +1,24 @@
+pub mod brood_ui;
 pub mod credits_ui;
+pub mod debug_ui;
 pub mod gamefield_ui;
+pub mod inspector_ui;
 pub mod menu_ui;
+pub mod minimap_ui;
 pub mod settings_menu;
+pub mod stats_ui;
 mod ui_util;
 pub mod upgrades;
 
 pub use gamefield_ui::GamefieldUI;
 
+pub use brood_ui::BroodChamberPlugin;
 pub use credits_ui::CreditsPlugin;
+pub use debug_ui::DebugMenuPlugin;
+pub use inspector_ui::AntInspectorPlugin;
 pub use menu_ui::MainMenuUI;
+pub use minimap_ui::MinimapPlugin;
 pub use settings_menu::SettingsMenuPlugin;
+pub use stats_ui::StatsPanelPlugin;
+pub use ui_util::{FocusNavigationPlugin, FocusedButton};
 pub use upgrades::UpgradePlugin;
@@ -1,19 +1,33 @@
 use bevy::{
-    ecs::system::SystemParam, prelude::*, ui::RelativeCursorPosition, window::PrimaryWindow,
+    ecs::system::SystemParam,
+    input::mouse::{MouseScrollUnit, MouseWheel},
+    prelude::*,
+    ui::RelativeCursorPosition,
+    window::PrimaryWindow,
 };
 
 use super::{
     menu_ui::UIAnchorNode,
-    ui_util::{into_pct, px, CoolDown, ProjectLocalStyle, UICommandsExt, ALL, MEDIUM},
+    ui_util::{
+        into_pct, px, CoolDown, Focusable, ProjectLocalStyle, UICommandsExt, ALL, MEDIUM, RED,
+        SMALL,
+    },
 };
 use bevy_nine_slice_ui::{NineSliceUiMaterialBundle, NineSliceUiTexture};
 use leafwing_input_manager::{
     action_state::{ActionState, ActionStateDriver},
+    input_map::InputMap,
     plugin::{InputManagerPlugin, ToggleActions},
+    prelude::MouseWheelDirection,
+    user_input::InputKind,
     Actionlike, InputManagerBundle,
 };
 
-use crate::{app_settings::ApplicationSettings, DisplaySettings, UIFocus, VolumeSettings};
+use crate::{
+    app_settings::{ApplicationSettings, CameraSettings},
+    playerinput::{describe_input, Control, ControlBindings},
+    DisplaySettings, UIFocus, VolumeSettings,
+};
 
 pub struct SettingsMenuPlugin;
 
@@ -22,19 +36,26 @@ impl Plugin for SettingsMenuPlugin {
         app.add_plugins(InputManagerPlugin::<SettingsMenuUIActions>::default())
             .add_plugins(InputManagerPlugin::<DisplaySettingsMenuUIActions>::default())
             .add_plugins(InputManagerPlugin::<AudioMenuUIActions>::default())
+            .init_resource::<Rebinding>()
             .add_systems(OnEnter(UIFocus::SettingsMenu), instantiate_settings_menu)
             .add_systems(
                 Update,
                 (
                     exit_settings_menu,
-                    activate_audio_settings_card,
-                    activate_display_settings_card,
+                    activate_settings_card,
                     (interactive_range_set, watch_audio_bars).chain(),
+                    (
+                        scroll_controls_list,
+                        rebind_controls,
+                        show_control_bindings,
+                    )
+                        .chain(),
                     (
                         show_active_window_settings,
                         (set_fullscreen_mode, set_window_resolution),
                     )
                         .chain(),
+                    (toggle_camera_options, show_camera_options).chain(),
                 ),
             )
             .add_systems(OnExit(UIFocus::SettingsMenu), settings_menu_teardown);
@@ -56,6 +77,10 @@ struct DisplaySettingsNode(Entity);
 #[derive(Component)]
 struct AudioSettingsNode(Entity);
 #[derive(Component)]
+struct ControlSettingsNode(Entity);
+#[derive(Component)]
+struct CameraSettingsNode(Entity);
+#[derive(Component)]
 struct Target(Entity);
 #[derive(Component)]
 struct UiToggle(bool);
@@ -71,10 +96,82 @@ pub struct UiRange(pub f32);
 #[derive(Component)]
 pub struct FillBar;
 
+#[derive(Component)]
+struct ControlsViewport;
+#[derive(Component)]
+struct ControlsList;
+#[derive(Component)]
+struct ControlRow(Control);
+#[derive(Component)]
+struct ControlBindingLabel(Control);
+#[derive(Component)]
+struct ControlsStatus;
+#[derive(Component)]
+struct ResetControls;
+
+// The checkbox for a camera option, and the check mark inside it.
+#[derive(Component)]
+struct CameraToggle(CameraOption);
+#[derive(Component)]
+struct CameraToggleCheck(CameraOption);
+
+#[derive(Clone, Copy)]
+enum CameraOption {
+    EdgeScroll,
+    DragPan,
+    ZoomToCursor,
+    Inertia,
+}
+impl CameraOption {
+    const ALL: [CameraOption; 4] = [
+        CameraOption::EdgeScroll,
+        CameraOption::DragPan,
+        CameraOption::ZoomToCursor,
+        CameraOption::Inertia,
+    ];
+    fn label(self) -> &'static str {
+        match self {
+            CameraOption::EdgeScroll => "Edge scroll:",
+            CameraOption::DragPan => "Drag to pan:",
+            CameraOption::ZoomToCursor => "Zoom to cursor:",
+            CameraOption::Inertia => "Glide:",
+        }
+    }
+    fn is_on(self, settings: &CameraSettings) -> bool {
+        match self {
+            CameraOption::EdgeScroll => settings.edge_scroll,
+            CameraOption::DragPan => settings.drag_pan,
+            CameraOption::ZoomToCursor => settings.zoom_to_cursor,
+            CameraOption::Inertia => settings.inertia,
+        }
+    }
+    fn flag(self, settings: &mut CameraSettings) -> &mut bool {
+        match self {
+            CameraOption::EdgeScroll => &mut settings.edge_scroll,
+            CameraOption::DragPan => &mut settings.drag_pan,
+            CameraOption::ZoomToCursor => &mut settings.zoom_to_cursor,
+            CameraOption::Inertia => &mut settings.inertia,
+        }
+    }
+}
+
+// Height of one row in the controls list, and how much of the list shows at once.
+const CONTROL_ROW_HEIGHT: f32 = 14.;
+const CONTROLS_VIEW_HEIGHT: f32 = 98.;
+
+// The control waiting on the player to press whatever they want it bound to, and what happened last time.
+#[derive(Resource, Default)]
+struct Rebinding {
+    listening: Option<Control>,
+    status: String,
+}
+
 #[derive(Actionlike, Clone, Debug, Copy, PartialEq, Eq, Hash, Reflect)]
 pub enum SettingsMenuUIActions {
     ToggleDisplaySettings,
     ToggleAudioSettings,
+    ToggleControlSettings,
+    ToggleCameraSettings,
     ExitSettings,
 }
 
@@ -98,8 +195,10 @@ fn instantiate_settings_menu(
     volume_settings: Res<VolumeSettings>,
     active_window_settings: ActiveWindowSettings,
     anchor: Res<UIAnchorNode>,
+    bindings: Res<ControlBindings>,
 ) {
     settings_menu_actions.enabled = true;
+    commands.insert_resource(Rebinding::default());
 
     let music_level = volume_settings.music_user_setting;
     let sfx_level = volume_settings.sfx_user_setting;
@@ -131,7 +230,12 @@ fn instantiate_settings_menu(
             },
             SettingsMenuRoot,
         ))
-        .insert(InputManagerBundle::<SettingsMenuUIActions>::default())
+        .insert(InputManagerBundle::<SettingsMenuUIActions> {
+            input_map: InputMap::default()
+                .insert(GamepadButtonType::East, SettingsMenuUIActions::ExitSettings)
+                .build(),
+            ..default()
+        })
         .id();
     let card_layout = commands
         .spawn(NodeBundle {
@@ -185,6 +289,7 @@ fn instantiate_settings_menu(
                 ..default()
             },
             Interaction::None,
+            Focusable(UIFocus::SettingsMenu),
         ))
         .insert(ActionStateDriver {
             action: SettingsMenuUIActions::ToggleDisplaySettings,
@@ -223,6 +328,7 @@ fn instantiate_settings_menu(
                 ..default()
             },
             Interaction::None,
+            Focusable(UIFocus::SettingsMenu),
         ))
         .insert(ActionStateDriver {
             action: SettingsMenuUIActions::ToggleAudioSettings,
@@ -239,6 +345,84 @@ fn instantiate_settings_menu(
             ..default()
         })
         .id();
+    let control_settings_tag = commands
+        .spawn((
+            NineSliceUiMaterialBundle {
+                style: Style {
+                    height: ALL,
+                    align_items: AlignItems::Center,
+                    align_content: AlignContent::Center,
+                    justify_content: JustifyContent::Center,
+                    padding: UiRect {
+                        left: px(6.),
+                        right: px(6.),
+                        top: px(6.),
+                        bottom: px(5.),
+                    },
+                    ..default()
+                },
+                nine_slice_texture: NineSliceUiTexture::from_image(
+                    asset_server.load("nine_slice/bgG_bMCG_soft_corner_flat_bottom.png"),
+                ),
+                ..default()
+            },
+            Interaction::None,
+            Focusable(UIFocus::SettingsMenu),
+        ))
+        .insert(ActionStateDriver {
+            action: SettingsMenuUIActions::ToggleControlSettings,
+            targets: settings_root_layout.into(),
+        })
+        .id();
+    let control_settings_label = commands
+        .spawn(TextBundle {
+            text: Text::from_section("Controls", TextStyle::local(MEDIUM, Color::WHITE)),
+            style: Style {
+                margin: UiRect::bottom(px(5.)),
+                ..default()
+            },
+            ..default()
+        })
+        .id();
+    let camera_settings_tag = commands
+        .spawn((
+            NineSliceUiMaterialBundle {
+                style: Style {
+                    height: ALL,
+                    align_items: AlignItems::Center,
+                    align_content: AlignContent::Center,
+                    justify_content: JustifyContent::Center,
+                    padding: UiRect {
+                        left: px(6.),
+                        right: px(6.),
+                        top: px(6.),
+                        bottom: px(5.),
+                    },
+                    ..default()
+                },
+                nine_slice_texture: NineSliceUiTexture::from_image(
+                    asset_server.load("nine_slice/bgG_bMCG_soft_corner_flat_bottom.png"),
+                ),
+                ..default()
+            },
+            Interaction::None,
+            Focusable(UIFocus::SettingsMenu),
+        ))
+        .insert(ActionStateDriver {
+            action: SettingsMenuUIActions::ToggleCameraSettings,
+            targets: settings_root_layout.into(),
+        })
+        .id();
+    let camera_settings_label = commands
+        .spawn(TextBundle {
+            text: Text::from_section("Camera", TextStyle::local(MEDIUM, Color::WHITE)),
+            style: Style {
+                margin: UiRect::bottom(px(5.)),
+                ..default()
+            },
+            ..default()
+        })
+        .id();
     let display_settings_grid = commands
         .spawn(NodeBundle {
             style: Style {
@@ -334,6 +518,7 @@ fn instantiate_settings_menu(
                     UiToggle(true),
                     Fullscreen,
                     Interaction::None,
+                    Focusable(UIFocus::SettingsMenu),
                 ))
                 .add_child(icon_checkbox);
         })
@@ -466,6 +651,15 @@ fn instantiate_settings_menu(
     let music_bar = make_audio_level_bar(&mut commands, 2, music_level, MusicVolumeLevel);
     let sfx_bar = make_audio_level_bar(&mut commands, 3, sfx_level, SFXVolumeLevel);
 
+    let control_settings_grid = make_controls_card(&mut commands, &asset_server, &bindings);
+    commands
+        .entity(control_settings_tag)
+        .insert(ControlSettingsNode(control_settings_grid));
+    let camera_settings_grid = make_camera_card(&mut commands, &asset_server);
+    commands
+        .entity(camera_settings_tag)
+        .insert(CameraSettingsNode(camera_settings_grid));
+
     let exit_button = commands
         .spawn((
             NineSliceUiMaterialBundle {
@@ -488,6 +682,7 @@ fn instantiate_settings_menu(
                 ..default()
             },
             Interaction::None,
+            Focusable(UIFocus::SettingsMenu),
         ))
         .insert(ActionStateDriver {
             action: SettingsMenuUIActions::ExitSettings,
@@ -504,6 +699,8 @@ fn instantiate_settings_menu(
         tags_layout,
         display_settings_grid,
         audio_settings_grid,
+        control_settings_grid,
+        camera_settings_grid,
         exit_button,
     ]);
     commands.entity(display_settings_grid).push_children(&[
@@ -520,15 +717,24 @@ fn instantiate_settings_menu(
         audio_settings_sfx_section_header,
         sfx_bar,
     ]);
-    commands
-        .entity(tags_layout)
-        .push_children(&[display_settings_tag, audio_settings_tag]);
+    commands.entity(tags_layout).push_children(&[
+        display_settings_tag,
+        audio_settings_tag,
+        control_settings_tag,
+        camera_settings_tag,
+    ]);
     commands
         .entity(display_settings_tag)
         .add_child(display_settings_label);
     commands
         .entity(audio_settings_tag)
         .add_child(audio_settings_label);
+    commands
+        .entity(control_settings_tag)
+        .add_child(control_settings_label);
+    commands
+        .entity(camera_settings_tag)
+        .add_child(camera_settings_label);
     commands.entity(exit_button).add_child(exit_button_label);
 }
 
@@ -549,53 +755,67 @@ fn settings_menu_teardown(
     });
     app_settings.save_app_settings();
 }
-fn activate_display_settings_card(
-    q: Query<&ActionState<SettingsMenuUIActions>>,
-    to_show: Query<&DisplaySettingsNode, With<ActionStateDriver<SettingsMenuUIActions>>>,
-    to_hide: Query<
-        &AudioSettingsNode,
-        (
-            With<ActionStateDriver<SettingsMenuUIActions>>,
-            Without<DisplaySettingsNode>,
-        ),
-    >,
-    mut toggle_display: Query<&mut Style>,
-    mut audio_settings_actions: ResMut<ToggleActions<AudioMenuUIActions>>,
-    mut display_settings_actions: ResMut<ToggleActions<DisplaySettingsMenuUIActions>>,
-) {
-    for n in q.iter() {
-        if n.just_pressed(SettingsMenuUIActions::ToggleDisplaySettings) {
-            audio_settings_actions.enabled = false;
-            display_settings_actions.enabled = true;
-            let [mut show_style, mut hide_style] =
-                toggle_display.many_mut([to_show.single().0, to_hide.single().0]);
-            show_style.display = Display::Grid;
-            hide_style.display = Display::None;
-        }
+// The card each tag shows, paired with the action for picking that tag.
+#[derive(SystemParam)]
+struct SettingsCards<'w, 's> {
+    display_tag: Query<'w, 's, &'static DisplaySettingsNode>,
+    audio_tag: Query<'w, 's, &'static AudioSettingsNode>,
+    control_tag: Query<'w, 's, &'static ControlSettingsNode>,
+    camera_tag: Query<'w, 's, &'static CameraSettingsNode>,
+}
+impl SettingsCards<'_, '_> {
+    fn get(&self) -> Option<[(Entity, SettingsMenuUIActions); 4]> {
+        Some([
+            (
+                self.display_tag.get_single().ok()?.0,
+                SettingsMenuUIActions::ToggleDisplaySettings,
+            ),
+            (
+                self.audio_tag.get_single().ok()?.0,
+                SettingsMenuUIActions::ToggleAudioSettings,
+            ),
+            (
+                self.control_tag.get_single().ok()?.0,
+                SettingsMenuUIActions::ToggleControlSettings,
+            ),
+            (
+                self.camera_tag.get_single().ok()?.0,
+                SettingsMenuUIActions::ToggleCameraSettings,
+            ),
+        ])
     }
 }
-fn activate_audio_settings_card(
+
+// Shows the card for whichever tag was picked, and hides the others.
+fn activate_settings_card(
     q: Query<&ActionState<SettingsMenuUIActions>>,
-    to_hide: Query<&DisplaySettingsNode, With<ActionStateDriver<SettingsMenuUIActions>>>,
-    to_show: Query<
-        &AudioSettingsNode,
-        (
-            With<ActionStateDriver<SettingsMenuUIActions>>,
-            Without<DisplaySettingsNode>,
-        ),
-    >,
+    cards: SettingsCards,
     mut toggle_display: Query<&mut Style>,
     mut audio_settings_actions: ResMut<ToggleActions<AudioMenuUIActions>>,
     mut display_settings_actions: ResMut<ToggleActions<DisplaySettingsMenuUIActions>>,
 ) {
-    for n in q.iter() {
-        if n.just_pressed(SettingsMenuUIActions::ToggleAudioSettings) {
-            audio_settings_actions.enabled = true;
-            display_settings_actions.enabled = false;
-            let [mut show_style, mut hide_style] =
-                toggle_display.many_mut([to_show.single().0, to_hide.single().0]);
-            show_style.display = Display::Grid;
-            hide_style.display = Display::None;
+    let Some(picked) = [
+        SettingsMenuUIActions::ToggleDisplaySettings,
+        SettingsMenuUIActions::ToggleAudioSettings,
+        SettingsMenuUIActions::ToggleControlSettings,
+        SettingsMenuUIActions::ToggleCameraSettings,
+    ]
+    .into_iter()
+    .find(|action| q.iter().any(|n| n.just_pressed(*action))) else {
+        return;
+    };
+    let Some(cards) = cards.get() else {
+        return;
+    };
+    audio_settings_actions.enabled = picked == SettingsMenuUIActions::ToggleAudioSettings;
+    display_settings_actions.enabled = picked == SettingsMenuUIActions::ToggleDisplaySettings;
+    for (card, shown_by) in cards {
+        if let Ok(mut style) = toggle_display.get_mut(card) {
+            style.display = if shown_by == picked {
+                Display::Grid
+            } else {
+                Display::None
+            };
         }
     }
 }
@@ -724,8 +944,6 @@ fn make_resolution_selection_component(
     resolution_options: Vec<(f32, f32)>,
     current: (f32, f32),
 ) -> Entity {
-    use super::ui_util::SMALL;
-
     let root = commands
         .spawn(NodeBundle {
             style: Style {
@@ -808,6 +1026,7 @@ fn make_resolution_selection_component(
                 },
                 UiResolutionInput(*entry),
                 Interaction::None,
+                Focusable(UIFocus::SettingsMenu),
             ))
             .id();
         commands.entity(root).add_child(section);
@@ -912,3 +1131,440 @@ fn set_fullscreen_mode(
         });
     }
 }
+
+// A checkbox for each of the camera's optional pan and zoom modes.
+fn make_camera_card(commands: &mut Commands, asset_server: &AssetServer) -> Entity {
+    let card = commands
+        .spawn(NodeBundle {
+            style: Style {
+                display: Display::None,
+                grid_column: GridPlacement::start(1),
+                grid_row: GridPlacement::start(1),
+                grid_template_columns: vec![GridTrack::fr(1.), GridTrack::px(44.)],
+                grid_template_rows: vec![GridTrack::px(22.); CameraOption::ALL.len()],
+                border: UiRect::all(px(2.)),
+                justify_self: JustifySelf::Start,
+                align_self: AlignSelf::Start,
+                width: ALL,
+                height: px(92.),
+                ..default()
+            },
+            background_color: Color::rgb_u8(89, 86, 82).into(),
+            border_color: Color::BLACK.into(),
+            ..default()
+        })
+        .id();
+    for (row, option) in CameraOption::ALL.into_iter().enumerate() {
+        let grid_row = GridPlacement::start(row as i16 + 1);
+        let header = commands
+            .spawn(NodeBundle {
+                style: Style {
+                    grid_column: GridPlacement::start(1),
+                    grid_row,
+                    align_items: AlignItems::Center,
+                    padding: UiRect::left(px(4.)),
+                    border: UiRect {
+                        top: px(0.),
+                        bottom: px(1.),
+                        right: px(1.),
+                        left: px(0.),
+                    },
+                    ..default()
+                },
+                border_color: Color::BLACK.into(),
+                ..default()
+            })
+            .id();
+        let label = commands
+            .make_text(option.label(), TextStyle::local(MEDIUM, Color::BLACK))
+            .id();
+        let checkbox_cell = commands
+            .spawn(NodeBundle {
+                style: Style {
+                    grid_column: GridPlacement::start(2),
+                    grid_row,
+                    align_items: AlignItems::Center,
+                    justify_content: JustifyContent::Center,
+                    border: UiRect {
+                        top: px(0.),
+                        bottom: px(1.),
+                        right: px(1.),
+                        left: px(0.),
+                    },
+                    ..default()
+                },
+                border_color: Color::BLACK.into(),
+                background_color: Color::rgb_u8(105, 106, 106).into(),
+                ..default()
+            })
+            .id();
+        let checkbox = commands
+            .spawn((
+                NineSliceUiMaterialBundle {
+                    style: Style {
+                        width: px(16.),
+                        height: px(16.),
+                        justify_content: JustifyContent::Center,
+                        align_items: AlignItems::Center,
+                        ..default()
+                    },
+                    nine_slice_texture: NineSliceUiTexture::from_image(
+                        asset_server.load("nine_slice/fullscreen_checkbox.png"),
+                    ),
+                    z_index: ZIndex::Local(20),
+                    ..default()
+                },
+                CameraToggle(option),
+                Interaction::None,
+                Focusable(UIFocus::SettingsMenu),
+            ))
+            .id();
+        // Filled in by show_camera_options.
+        let check = commands
+            .spawn((
+                ImageBundle {
+                    style: Style {
+                        width: into_pct(0.5),
+                        height: into_pct(0.5),
+                        ..default()
+                    },
+                    ..default()
+                },
+                CameraToggleCheck(option),
+            ))
+            .id();
+        commands.entity(header).add_child(label);
+        commands.entity(checkbox).add_child(check);
+        commands.entity(checkbox_cell).add_child(checkbox);
+        commands
+            .entity(card)
+            .push_children(&[header, checkbox_cell]);
+    }
+    card
+}
+
+fn toggle_camera_options(
+    q: Query<(&Interaction, &CameraToggle), Changed<Interaction>>,
+    mut settings: ResMut<CameraSettings>,
+) {
+    for (interaction, CameraToggle(option)) in q.iter() {
+        if matches!(interaction, Interaction::Pressed) {
+            let flag = option.flag(&mut settings);
+            *flag = !*flag;
+        }
+    }
+}
+
+fn show_camera_options(
+    settings: Res<CameraSettings>,
+    asset_server: Res<AssetServer>,
+    added: Query<(), Added<CameraToggleCheck>>,
+    mut checks: Query<(&mut UiImage, &CameraToggleCheck)>,
+) {
+    if !settings.is_changed() && added.is_empty() {
+        return;
+    }
+    for (mut image, CameraToggleCheck(option)) in checks.iter_mut() {
+        let checkbox_name = if option.is_on(&settings) {
+            "green_check_icon.png"
+        } else {
+            "empty_5x5.png"
+        };
+        image.texture = asset_server.load(checkbox_name);
+    }
+}
+
+// A scrolling list with a row per control - click one, then press whatever it should be bound to.
+fn make_controls_card(
+    commands: &mut Commands,
+    asset_server: &AssetServer,
+    bindings: &ControlBindings,
+) -> Entity {
+    let card = commands
+        .spawn(NodeBundle {
+            style: Style {
+                display: Display::None,
+                grid_column: GridPlacement::start(1),
+                grid_row: GridPlacement::start(1),
+                grid_template_columns: vec![GridTrack::fr(1.)],
+                grid_template_rows: vec![GridTrack::px(CONTROLS_VIEW_HEIGHT), GridTrack::fr(1.)],
+                border: UiRect::all(px(2.)),
+                justify_self: JustifySelf::Start,
+                align_self: AlignSelf::Start,
+                width: ALL,
+                height: px(122.),
+                ..default()
+            },
+            background_color: Color::rgb_u8(89, 86, 82).into(),
+            border_color: Color::BLACK.into(),
+            ..default()
+        })
+        .id();
+    let viewport = commands
+        .spawn((
+            NodeBundle {
+                style: Style {
+                    grid_row: GridPlacement::start(1),
+                    overflow: Overflow::clip(),
+                    border: UiRect::bottom(px(1.)),
+                    ..default()
+                },
+                border_color: Color::BLACK.into(),
+                ..default()
+            },
+            ControlsViewport,
+            RelativeCursorPosition::default(),
+        ))
+        .id();
+    let list = commands
+        .spawn((
+            NodeBundle {
+                style: Style {
+                    width: ALL,
+                    flex_direction: FlexDirection::Column,
+                    top: px(0.),
+                    ..default()
+                },
+                ..default()
+            },
+            ControlsList,
+        ))
+        .id();
+    let rows = Control::all()
+        .into_iter()
+        .map(|control| {
+            let name = commands
+                .make_text(control.name(), TextStyle::local(SMALL, Color::BLACK))
+                .id();
+            let binding = commands
+                .make_text(
+                    &describe_input(&bindings.get(control)),
+                    TextStyle::local(SMALL, Color::BLACK),
+                )
+                .insert(ControlBindingLabel(control))
+                .id();
+            commands
+                .spawn((
+                    NodeBundle {
+                        style: Style {
+                            height: px(CONTROL_ROW_HEIGHT),
+                            flex_shrink: 0.,
+                            justify_content: JustifyContent::SpaceBetween,
+                            align_items: AlignItems::Center,
+                            padding: UiRect::horizontal(px(4.)),
+                            ..default()
+                        },
+                        ..default()
+                    },
+                    ControlRow(control),
+                    Interaction::None,
+                    Focusable(UIFocus::SettingsMenu),
+                ))
+                .push_children(&[name, binding])
+                .id()
+        })
+        .collect::<Vec<Entity>>();
+    let footer = commands
+        .spawn(NodeBundle {
+            style: Style {
+                grid_row: GridPlacement::start(2),
+                justify_content: JustifyContent::SpaceBetween,
+                align_items: AlignItems::Center,
+                padding: UiRect::horizontal(px(4.)),
+                ..default()
+            },
+            ..default()
+        })
+        .id();
+    let status = commands
+        .make_text("", TextStyle::local(SMALL, Color::WHITE))
+        .insert(ControlsStatus)
+        .id();
+    let reset_button = commands
+        .spawn((
+            NineSliceUiMaterialBundle {
+                style: Style {
+                    height: px(16.),
+                    padding: UiRect::horizontal(px(4.)),
+                    align_items: AlignItems::Center,
+                    justify_content: JustifyContent::Center,
+                    ..default()
+                },
+                nine_slice_texture: NineSliceUiTexture::from_image(
+                    asset_server.load("nine_slice/settings_menu_exit_button.png"),
+                ),
+                ..default()
+            },
+            Interaction::None,
+            Focusable(UIFocus::SettingsMenu),
+            ResetControls,
+        ))
+        .id();
+    let reset_label = commands
+        .make_text("Defaults", TextStyle::local(SMALL, Color::WHITE))
+        .id();
+
+    commands.entity(card).push_children(&[viewport, footer]);
+    commands.entity(viewport).add_child(list);
+    commands.entity(list).push_children(&rows);
+    commands.entity(footer).push_children(&[status, reset_button]);
+    commands.entity(reset_button).add_child(reset_label);
+    card
+}
+
+// The keys, buttons and wheel clicks that went down this frame, for binding to whatever control is listening.
+#[derive(SystemParam)]
+struct RawButtons<'w, 's> {
+    keys: Res<'w, Input<KeyCode>>,
+    mouse: Res<'w, Input<MouseButton>>,
+    wheel: EventReader<'w, 's, MouseWheel>,
+}
+impl RawButtons<'_, '_> {
+    fn cancelled(&self) -> bool {
+        self.keys.just_pressed(KeyCode::Escape)
+    }
+    // Always drains the wheel, so that scrolling done before the player started rebinding can't get bound.
+    fn just_pressed(&mut self) -> Option<InputKind> {
+        let wheel = self.wheel.read().map(|event| event.y).sum::<f32>();
+        self.keys
+            .get_just_pressed()
+            .next()
+            .map(|key| InputKind::Keyboard(*key))
+            .or_else(|| {
+                self.mouse
+                    .get_just_pressed()
+                    .next()
+                    .map(|button| InputKind::Mouse(*button))
+            })
+            .or(match wheel {
+                y if y > 0. => Some(InputKind::MouseWheel(MouseWheelDirection::Up)),
+                y if y < 0. => Some(InputKind::MouseWheel(MouseWheelDirection::Down)),
+                _ => None,
+            })
+    }
+}
+
+fn scroll_controls_list(
+    mut wheel: EventReader<MouseWheel>,
+    rebinding: Res<Rebinding>,
+    viewport: Query<&RelativeCursorPosition, With<ControlsViewport>>,
+    mut list: Query<&mut Style, With<ControlsList>>,
+) {
+    let scrolled = wheel
+        .read()
+        .map(|event| match event.unit {
+            MouseScrollUnit::Line => event.y * CONTROL_ROW_HEIGHT,
+            MouseScrollUnit::Pixel => event.y,
+        })
+        .sum::<f32>();
+    // While listening, the wheel is something to bind rather than something to scroll with.
+    if scrolled == 0. || rebinding.listening.is_some() {
+        return;
+    }
+    if !viewport.iter().any(|cursor| cursor.mouse_over()) {
+        return;
+    }
+    let max_scroll =
+        (Control::all().len() as f32 * CONTROL_ROW_HEIGHT - CONTROLS_VIEW_HEIGHT).max(0.);
+    for mut style in list.iter_mut() {
+        let top = match style.top {
+            Val::Px(top) => top,
+            _ => 0.,
+        };
+        style.top = px((top + scrolled).clamp(-max_scroll, 0.));
+    }
+}
+
+fn rebind_controls(
+    mut rebinding: ResMut<Rebinding>,
+    mut bindings: ResMut<ControlBindings>,
+    mut raw: RawButtons,
+    rows: Query<(&Interaction, &ControlRow), Changed<Interaction>>,
+    hovered: Query<(&Interaction, &ControlRow)>,
+    reset: Query<&Interaction, (Changed<Interaction>, With<ResetControls>)>,
+) {
+    let pressed = raw.just_pressed();
+    if let Some(control) = rebinding.listening {
+        // Left click only binds when it's on the row that's listening - anywhere else it's the player backing out.
+        let on_row = hovered.iter().any(|(interaction, ControlRow(row))| {
+            *row == control && !matches!(interaction, Interaction::None)
+        });
+        let clicked_away = pressed == Some(InputKind::Mouse(MouseButton::Left)) && !on_row;
+        // Escape is kept back for cancelling, so it can't be bound to anything new.
+        if raw.cancelled() || clicked_away {
+            rebinding.listening = None;
+            rebinding.status = "Cancelled".into();
+            return;
+        }
+        let Some(input) = pressed else {
+            return;
+        };
+        rebinding.listening = None;
+        rebinding.status = match bindings.rebind(control, input) {
+            Some(swapped) => format!("Swapped with {}", swapped.name()),
+            None => format!("{} set", control.name()),
+        };
+        info!("bound {:?} to {:?}", control, input);
+        // The click (or key) that finished rebinding shouldn't also start the next one.
+        return;
+    }
+    if reset
+        .iter()
+        .any(|interaction| matches!(interaction, Interaction::Pressed))
+    {
+        *bindings = ControlBindings::default();
+        rebinding.status = "Controls reset".into();
+        return;
+    }
+    if let Some((_, ControlRow(control))) = rows
+        .iter()
+        .find(|(interaction, _)| matches!(interaction, Interaction::Pressed))
+    {
+        rebinding.listening = Some(*control);
+        rebinding.status = "Press any key, Esc cancels".into();
+    }
+}
+
+type ControlRowHighlightQuery<'w, 's> = Query<
+    'w,
+    's,
+    (&'static Interaction, &'static mut BackgroundColor),
+    (With<ControlRow>, Changed<Interaction>),
+>;
+
+fn show_control_bindings(
+    bindings: Res<ControlBindings>,
+    rebinding: Res<Rebinding>,
+    mut rows: ControlRowHighlightQuery,
+    mut labels: Query<(&mut Text, &ControlBindingLabel)>,
+    mut status: Query<&mut Text, (With<ControlsStatus>, Without<ControlBindingLabel>)>,
+) {
+    for (interaction, mut color) in rows.iter_mut() {
+        *color = match interaction {
+            Interaction::None => Color::NONE,
+            _ => Color::rgb_u8(105, 106, 106),
+        }
+        .into();
+    }
+    if !bindings.is_changed() && !rebinding.is_changed() {
+        return;
+    }
+    let conflicts = bindings.conflicts();
+    for (mut text, ControlBindingLabel(control)) in labels.iter_mut() {
+        let (value, color) = if rebinding.listening == Some(*control) {
+            ("...".into(), Color::WHITE)
+        } else if conflicts.contains(control) {
+            (describe_input(&bindings.get(*control)), RED())
+        } else {
+            (describe_input(&bindings.get(*control)), Color::BLACK)
+        };
+        text.sections[0].value = value;
+        text.sections[0].style.color = color;
+    }
+    for mut text in status.iter_mut() {
+        text.sections[0].value = match (rebinding.status.as_str(), conflicts.first()) {
+            ("", Some(conflict)) => format!("{} clashes", conflict.name()),
+            (status, _) => status.into(),
+        };
+    }
+}
@@ -0,0 +1,307 @@
+use bevy::prelude::*;
+use bevy_nine_slice_ui::*;
+use leafwing_input_manager::prelude::ActionState;
+
+use super::{gamefield_ui::GamefieldUIRoot, ui_util::*};
+use crate::{
+    playerinput::GamefieldActions,
+    stats::{ColonyStats, StatSample},
+    UIFocus,
+};
+
+pub struct StatsPanelPlugin;
+
+impl Plugin for StatsPanelPlugin {
+    fn build(&self, app: &mut App) {
+        app.add_systems(
+            Update,
+            (
+                spawn_stats_panel,
+                toggle_stats_panel.run_if(in_state(UIFocus::Gamefield)),
+                draw_stat_graphs,
+            )
+                .chain(),
+        );
+    }
+}
+
+const PLOT_WIDTH: f32 = 240.0;
+const PLOT_HEIGHT: f32 = 60.0;
+// The most recent samples that fit across a plot, one column each.
+const GRAPH_POINTS: usize = 60;
+const LINE_THICKNESS: f32 = 2.0;
+
+#[derive(Clone, Copy, PartialEq, Eq)]
+enum GraphKind {
+    Food,
+    Population,
+    Flow,
+    Labor,
+}
+impl GraphKind {
+    fn title(&self) -> &'static str {
+        match self {
+            GraphKind::Food => "Food",
+            GraphKind::Population => "Population",
+            GraphKind::Flow => "Per minute",
+            GraphKind::Labor => "Labor",
+        }
+    }
+    fn series(&self) -> Vec<(&'static str, Color)> {
+        match self {
+            GraphKind::Food => vec![("stored", GREEN())],
+            GraphKind::Population => vec![("ants", PURPLE())],
+            GraphKind::Flow => vec![
+                ("births", GREEN()),
+                ("deaths", RED()),
+                ("deliveries", Color::rgb_u8(91, 110, 225)),
+            ],
+            GraphKind::Labor => vec![
+                ("foragers", Color::rgb_u8(143, 86, 59)),
+                ("nursemaids", Color::rgb_u8(217, 160, 102)),
+                ("idle", Color::GRAY),
+            ],
+        }
+    }
+    fn values(&self, sample: &StatSample) -> Vec<f32> {
+        match self {
+            GraphKind::Food => vec![sample.food as f32],
+            GraphKind::Population => vec![sample.population as f32],
+            GraphKind::Flow => vec![
+                sample.births_per_minute,
+                sample.deaths_per_minute,
+                sample.deliveries_per_minute,
+            ],
+            GraphKind::Labor => vec![
+                sample.foragers as f32,
+                sample.nursemaids as f32,
+                sample.idle as f32,
+            ],
+        }
+    }
+    fn format(&self, value: f32) -> String {
+        match self {
+            GraphKind::Flow => format!("{:.1}", value),
+            _ => format!("{:.0}", value),
+        }
+    }
+}
+
+#[derive(Component)]
+struct StatsPanel;
+#[derive(Component)]
+struct StatsButton;
+#[derive(Component)]
+struct StatPlot(GraphKind);
+#[derive(Component)]
+struct StatLegend(GraphKind);
+
+// A button under the calendar, and the (hidden) panel it opens over the middle of the gamefield.
+fn spawn_stats_panel(
+    mut commands: Commands,
+    asset_server: Res<AssetServer>,
+    root: Query<Entity, Added<GamefieldUIRoot>>,
+) {
+    let Ok(root) = root.get_single() else {
+        return;
+    };
+    let button = commands
+        .spawn((
+            NineSliceUiMaterialBundle {
+                style: Style {
+                    position_type: PositionType::Absolute,
+                    left: px(8.),
+                    top: px(60.),
+                    padding: UiRect::axes(px(10.), px(4.)),
+                    ..default()
+                },
+                nine_slice_texture: NineSliceUiTexture::from_image(
+                    asset_server.load("nine_slice/bgW_bG_soft_corner_2.png"),
+                ),
+                ..default()
+            },
+            Interaction::None,
+            Focusable(UIFocus::Gamefield),
+            StatsButton,
+        ))
+        .id();
+    let button_text = commands
+        .make_text("Stats [Tab]", TextStyle::local(MEDIUM, Color::BLACK))
+        .id();
+
+    let panel = commands
+        .spawn((
+            NineSliceUiMaterialBundle {
+                style: Style {
+                    display: Display::None,
+                    position_type: PositionType::Absolute,
+                    left: Val::Percent(50.),
+                    top: Val::Percent(50.),
+                    margin: UiRect {
+                        left: px(-(PLOT_WIDTH + 24.)),
+                        top: px(-(PLOT_HEIGHT + 60.)),
+                        ..default()
+                    },
+                    width: px(PLOT_WIDTH * 2. + 48.),
+                    padding: UiRect::all(px(8.)),
+                    flex_direction: FlexDirection::Row,
+                    flex_wrap: FlexWrap::Wrap,
+                    column_gap: px(16.),
+                    row_gap: px(8.),
+                    ..default()
+                },
+                nine_slice_texture: NineSliceUiTexture::from_image(
+                    asset_server.load("nine_slice/bgW_bG_soft_corner_2.png"),
+                ),
+                z_index: ZIndex::Global(50),
+                ..default()
+            },
+            StatsPanel,
+            Name::new("Stats Panel"),
+        ))
+        .id();
+    let graphs = [
+        GraphKind::Food,
+        GraphKind::Population,
+        GraphKind::Flow,
+        GraphKind::Labor,
+    ]
+    .map(|kind| spawn_graph(&mut commands, kind));
+
+    commands.entity(root).push_children(&[button, panel]);
+    commands.entity(button).add_child(button_text);
+    commands.entity(panel).push_children(&graphs);
+}
+
+fn spawn_graph(commands: &mut Commands, kind: GraphKind) -> Entity {
+    let graph = commands
+        .spawn(NodeBundle {
+            style: Style {
+                width: px(PLOT_WIDTH),
+                flex_direction: FlexDirection::Column,
+                row_gap: px(2.),
+                ..default()
+            },
+            ..default()
+        })
+        .id();
+    let mut sections = vec![(kind.title(), TextStyle::local(SMALL, Color::BLACK))];
+    sections.extend(
+        kind.series()
+            .into_iter()
+            .map(|(_, color)| ("", TextStyle::local(SMALL, color))),
+    );
+    let legend = commands
+        .make_text_sections(sections)
+        .insert(StatLegend(kind))
+        .id();
+    let plot = commands
+        .spawn((
+            NodeBundle {
+                style: Style {
+                    width: px(PLOT_WIDTH),
+                    height: px(PLOT_HEIGHT),
+                    overflow: Overflow::clip(),
+                    ..default()
+                },
+                background_color: Color::rgba(0.0, 0.0, 0.0, 0.08).into(),
+                ..default()
+            },
+            StatPlot(kind),
+        ))
+        .id();
+    commands.entity(graph).push_children(&[legend, plot]);
+    graph
+}
+
+fn toggle_stats_panel(
+    actions: Query<&ActionState<GamefieldActions>>,
+    button: Query<&Interaction, (Changed<Interaction>, With<StatsButton>)>,
+    mut panel: Query<&mut Style, With<StatsPanel>>,
+) {
+    let pressed = actions
+        .iter()
+        .any(|action| action.just_pressed(GamefieldActions::ToggleStats))
+        || button
+            .iter()
+            .any(|interaction| matches!(interaction, Interaction::Pressed));
+    if !pressed {
+        return;
+    }
+    for mut style in panel.iter_mut() {
+        style.display = match style.display {
+            Display::None => Display::Flex,
+            _ => Display::None,
+        };
+    }
+}
+
+// Each series is drawn as a stepped line - one column per sample, spanning from the previous value to this one.
+// Plots are only rebuilt when a new sample comes in.
+fn draw_stat_graphs(
+    mut commands: Commands,
+    stats: Res<ColonyStats>,
+    mut drawn: Local<usize>,
+    plots: Query<(Entity, &StatPlot)>,
+    mut legends: Query<(&mut Text, &StatLegend)>,
+) {
+    if stats.taken() == *drawn || plots.is_empty() {
+        return;
+    }
+    *drawn = stats.taken();
+    let samples = stats.samples();
+    let recent = samples
+        .range(samples.len().saturating_sub(GRAPH_POINTS)..)
+        .collect::<Vec<&StatSample>>();
+    let column_width = PLOT_WIDTH / GRAPH_POINTS as f32;
+
+    for (plot, StatPlot(kind)) in plots.iter() {
+        let values = recent
+            .iter()
+            .map(|sample| kind.values(sample))
+            .collect::<Vec<Vec<f32>>>();
+        let peak = values.iter().flatten().fold(1.0_f32, |a, b| a.max(*b));
+        let to_y = |value: f32| value / peak * (PLOT_HEIGHT - LINE_THICKNESS);
+
+        commands.entity(plot).despawn_descendants();
+        let mut columns = Vec::new();
+        for (series, (_, color)) in kind.series().into_iter().enumerate() {
+            for (i, sample_values) in values.iter().enumerate() {
+                let y = to_y(sample_values[series]);
+                let previous = if i == 0 {
+                    y
+                } else {
+                    to_y(values[i - 1][series])
+                };
+                let column = commands
+                    .spawn(NodeBundle {
+                        style: Style {
+                            position_type: PositionType::Absolute,
+                            left: px(i as f32 * column_width),
+                            bottom: px(y.min(previous)),
+                            width: px(column_width),
+                            height: px((y - previous).abs().max(LINE_THICKNESS)),
+                            ..default()
+                        },
+                        background_color: color.into(),
+                        ..default()
+                    })
+                    .id();
+                columns.push(column);
+            }
+        }
+        commands.entity(plot).push_children(&columns);
+
+        for (mut text, StatLegend(legend_kind)) in legends.iter_mut() {
+            if legend_kind != kind {
+                continue;
+            }
+            text.sections[0].value = format!("{} (peak {})", kind.title(), kind.format(peak));
+            let latest = values.last().cloned().unwrap_or_default();
+            for (series, (name, _)) in kind.series().into_iter().enumerate() {
+                let value = latest.get(series).copied().unwrap_or(0.0);
+                text.sections[series + 1].value = format!("  {}: {}", name, kind.format(value));
+            }
+        }
+    }
+}
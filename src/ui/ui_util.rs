@@ -1,6 +1,9 @@
 use std::time::Duration;
 
-use bevy::{ecs::system::EntityCommands, prelude::*};
+use bevy::{ecs::system::EntityCommands, prelude::*, window::CursorMoved};
+use leafwing_input_manager::{plugin::InputManagerSystem, prelude::*};
+
+use crate::UIFocus;
 
 pub const SMALL: f32 = 12.;
 pub const MEDIUM: f32 = 16.;
@@ -231,4 +234,224 @@ impl CoolDown {
         self.cooling_down = false;
         self.elapsed.reset();
     }
-}
\ No newline at end of file
+}
+
+// Fires once when something is first held, then keeps firing - faster the longer it's held.
+#[derive(Default)]
+pub struct HoldToRepeat {
+    holding: bool,
+    held_for: f32,
+    until_next: f32,
+}
+impl HoldToRepeat {
+    const FIRST_REPEAT_DELAY: f32 = 0.4;
+    const REPEAT_INTERVAL: f32 = 0.2;
+    const FASTEST_INTERVAL: f32 = 0.04;
+
+    pub fn tick(&mut self, held: bool, delta: Duration) -> bool {
+        if !held {
+            self.holding = false;
+            self.held_for = 0.;
+            self.until_next = 0.;
+            return false;
+        }
+        self.held_for += delta.as_secs_f32();
+        if !self.holding {
+            self.holding = true;
+            self.until_next = Self::FIRST_REPEAT_DELAY;
+            return true;
+        }
+        self.until_next -= delta.as_secs_f32();
+        if self.until_next > 0. {
+            return false;
+        }
+        self.until_next =
+            (Self::REPEAT_INTERVAL / (1. + self.held_for * 2.)).max(Self::FASTEST_INTERVAL);
+        true
+    }
+}
+// Lets a gamepad get around the UI - the D-pad moves focus between buttons and South presses whichever has it.
+pub struct FocusNavigationPlugin;
+
+impl Plugin for FocusNavigationPlugin {
+    fn build(&self, app: &mut App) {
+        app.add_plugins(InputManagerPlugin::<FocusNavActions>::default())
+            .init_resource::<FocusedButton>()
+            .add_systems(Startup, spawn_focus_navigator)
+            .add_systems(
+                PreUpdate,
+                press_focused_button
+                    .after(InputManagerSystem::Update)
+                    .before(InputManagerSystem::ManualControl),
+            )
+            .add_systems(Update, (move_focus, highlight_focused_button).chain());
+    }
+}
+
+const FOCUS_OUTLINE: Outline = Outline::new(Val::Px(2.), Val::Px(1.), Color::YELLOW);
+
+#[derive(Actionlike, Clone, Debug, Copy, PartialEq, Eq, Hash, Reflect)]
+pub enum FocusNavActions {
+    Up,
+    Down,
+    Left,
+    Right,
+    Press,
+    Back,
+}
+
+// A button the gamepad can focus, while the UI is focused on the given part of the game.
+#[derive(Component)]
+pub struct Focusable(pub UIFocus);
+
+#[derive(Resource, Default)]
+pub struct FocusedButton {
+    entity: Option<Entity>,
+    // The button we pressed last frame, and still need to let go of.
+    pressed: Option<Entity>,
+}
+impl FocusedButton {
+    pub fn entity(&self) -> Option<Entity> {
+        self.entity
+    }
+}
+
+fn spawn_focus_navigator(mut commands: Commands) {
+    commands.spawn((
+        InputManagerBundle::<FocusNavActions> {
+            input_map: InputMap::new([
+                (GamepadButtonType::DPadUp, FocusNavActions::Up),
+                (GamepadButtonType::DPadDown, FocusNavActions::Down),
+                (GamepadButtonType::DPadLeft, FocusNavActions::Left),
+                (GamepadButtonType::DPadRight, FocusNavActions::Right),
+                (GamepadButtonType::South, FocusNavActions::Press),
+                (GamepadButtonType::East, FocusNavActions::Back),
+            ]),
+            ..default()
+        },
+        Name::new("Focus Navigator"),
+    ));
+}
+
+// Presses go through Interaction, so everything that works with a mouse click works with the gamepad too - ActionStateDriver buttons included.
+// This has to land after bevy works out the mouse's interactions, and before LWIM turns them into actions.
+fn press_focused_button(
+    mut focused: ResMut<FocusedButton>,
+    actions: Query<&ActionState<FocusNavActions>>,
+    mut buttons: Query<&mut Interaction, With<Focusable>>,
+) {
+    // Bevy only lets go of pressed buttons when the left mouse button comes up, so we have to let go of ours ourselves.
+    if let Some(mut interaction) = focused
+        .pressed
+        .and_then(|entity| buttons.get_mut(entity).ok())
+    {
+        if *interaction == Interaction::Pressed {
+            *interaction = Interaction::None;
+        }
+    }
+    if focused.pressed.is_some() {
+        focused.pressed = None;
+    }
+    if !actions
+        .iter()
+        .any(|action| action.just_pressed(FocusNavActions::Press))
+    {
+        return;
+    }
+    // Buttons can lose their Interaction while they're unusable, e.g. upgrades we can't afford.
+    if let Some(entity) = focused.entity {
+        if let Ok(mut interaction) = buttons.get_mut(entity) {
+            *interaction = Interaction::Pressed;
+            focused.pressed = Some(entity);
+        }
+    }
+}
+
+fn move_focus(
+    mut focused: ResMut<FocusedButton>,
+    ui_focus: Res<State<UIFocus>>,
+    actions: Query<&ActionState<FocusNavActions>>,
+    mut cursor_moved: EventReader<CursorMoved>,
+    buttons: Query<(Entity, &Focusable, &Node, &GlobalTransform, &ViewVisibility)>,
+) {
+    let candidates = buttons
+        .iter()
+        .filter(|(_, focusable, node, _, visibility)| {
+            focusable.0 == *ui_focus.get() && visibility.get() && node.size() != Vec2::ZERO
+        })
+        .map(|(entity, _, _, transform, _)| (entity, transform.translation().truncate()))
+        .collect::<Vec<(Entity, Vec2)>>();
+    let current = focused
+        .entity
+        .and_then(|entity| candidates.iter().find(|(candidate, _)| *candidate == entity));
+    let pressed = |nav: FocusNavActions| actions.iter().any(|action| action.just_pressed(nav));
+
+    // UI space runs top to bottom.
+    let direction = [
+        (FocusNavActions::Up, Vec2::NEG_Y),
+        (FocusNavActions::Down, Vec2::Y),
+        (FocusNavActions::Left, Vec2::NEG_X),
+        (FocusNavActions::Right, Vec2::X),
+    ]
+    .into_iter()
+    .find(|(nav, _)| pressed(*nav))
+    .map(|(_, direction)| direction);
+
+    let next = match (current, direction) {
+        // Moving the mouse hands the UI back to it.
+        _ if cursor_moved.read().count() > 0 => None,
+        _ if pressed(FocusNavActions::Back) => None,
+        (Some((entity, from)), Some(direction)) => {
+            Some(nearest_in_direction(*from, direction, &candidates).unwrap_or(*entity))
+        }
+        (Some((entity, _)), None) => Some(*entity),
+        // With nothing focused yet, the D-pad picks out the top left button. South is left alone, it's a gamefield click.
+        (None, Some(_)) => first_button(&candidates),
+        (None, None) => None,
+    };
+    if focused.entity != next {
+        focused.entity = next;
+    }
+}
+
+fn first_button(candidates: &[(Entity, Vec2)]) -> Option<Entity> {
+    candidates
+        .iter()
+        .min_by(|(_, a), (_, b)| a.y.total_cmp(&b.y).then(a.x.total_cmp(&b.x)))
+        .map(|(entity, _)| *entity)
+}
+
+// Favors buttons straight ahead over ones that are closer but off to the side.
+fn nearest_in_direction(
+    from: Vec2,
+    direction: Vec2,
+    candidates: &[(Entity, Vec2)],
+) -> Option<Entity> {
+    candidates
+        .iter()
+        .filter_map(|(entity, pos)| {
+            let offset = *pos - from;
+            let ahead = offset.dot(direction);
+            let aside = (offset - direction * ahead).length();
+            (ahead > 1.).then_some((*entity, ahead + 2. * aside))
+        })
+        .min_by(|(_, a), (_, b)| a.total_cmp(b))
+        .map(|(entity, _)| entity)
+}
+
+fn highlight_focused_button(
+    mut commands: Commands,
+    focused: Res<FocusedButton>,
+    mut highlighted: Local<Option<Entity>>,
+) {
+    if !focused.is_changed() || *highlighted == focused.entity {
+        return;
+    }
+    if let Some(mut entity) = highlighted.and_then(|entity| commands.get_entity(entity)) {
+        entity.remove::<Outline>();
+    }
+    if let Some(mut entity) = focused.entity.and_then(|entity| commands.get_entity(entity)) {
+        entity.insert(FOCUS_OUTLINE);
+    }
+    *highlighted = focused.entity;
+}
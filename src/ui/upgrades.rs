@@ -1,14 +1,18 @@
 use bevy::prelude::*;
 
 use bevy_nine_slice_ui::{NineSliceUiMaterialBundle, NineSliceUiTexture};
-use std::marker::PhantomData;
+use leafwing_input_manager::action_state::{ActionState, ActionStateDriver};
+use std::{marker::PhantomData, time::Duration};
 
-use super::ui_util::{px, ProjectLocalStyle, ALL, GREEN, SMALL};
+use super::ui_util::{px, Focusable, HoldToRepeat, ProjectLocalStyle, ALL, GREEN, SMALL};
 
 use crate::{
     ant::AntSettings,
     colony::{AntCapacity, Colony, MaxFood, UpgradeStringIndex},
     food::FoodQuant,
+    playerinput::{describe_input, Control, ControlBindings, GamefieldActions},
+    queen::QueenSettings,
+    UIFocus,
 };
 
 pub struct UpgradePlugin;
@@ -17,7 +21,12 @@ impl Plugin for UpgradePlugin {
     fn build(&self, app: &mut App) {
         app.add_systems(
             Update,
-            (AntMaxPop::init, ColonyMaxFood::init, AntCarryCapacity::init)
+            (
+                AntMaxPop::init,
+                ColonyMaxFood::init,
+                AntCarryCapacity::init,
+                QueenFertility::init,
+            )
                 .run_if(when_colony_exists.and_then(run_once())),
         )
         .add_systems(
@@ -36,21 +45,30 @@ impl Plugin for UpgradePlugin {
                 AntCarryCapacity::progress_bar_display_effect,
                 AntCarryCapacity::set_upgrade_button_able,
                 AntCarryCapacity::set_maxed.run_if(AntCarryCapacity::is_maxed.and_then(run_once())),
+                QueenFertility::upgrade_colony,
+                QueenFertility::progress_bar_update,
+                QueenFertility::progress_bar_display_effect,
+                QueenFertility::set_upgrade_button_able,
+                QueenFertility::set_maxed.run_if(QueenFertility::is_maxed.and_then(run_once())),
                 #[cfg(target_arch = "wasm32")]
                 AntMaxPop::set_maxed.run_if(AntMaxPop::is_maxed.and_then(run_once())),
+                show_upgrade_hotkeys,
             ),
         );
     }
 }
 
+// The buttons drive their upgrade's action on `action_target`, the same action the hotkey triggers.
 pub fn spawn_upgrade_buttons(
     commands: &mut Commands,
     asset_server: &Res<AssetServer>,
-) -> [Entity; 3] {
+    action_target: Entity,
+) -> [Entity; 4] {
     [
-        AntMaxPop::spawn_button(commands, asset_server),
-        ColonyMaxFood::spawn_button(commands, asset_server),
-        AntCarryCapacity::spawn_button(commands, asset_server),
+        AntMaxPop::spawn_button(commands, asset_server, action_target),
+        ColonyMaxFood::spawn_button(commands, asset_server, action_target),
+        AntCarryCapacity::spawn_button(commands, asset_server, action_target),
+        QueenFertility::spawn_button(commands, asset_server, action_target),
     ]
 }
 
@@ -58,6 +76,23 @@ fn when_colony_exists(q: Query<&Colony>) -> bool {
     q.get_single().is_ok()
 }
 
+#[derive(Component)]
+struct UpgradeHotkeyLabel(GamefieldActions);
+
+fn show_upgrade_hotkeys(
+    bindings: Res<ControlBindings>,
+    added: Query<(), Added<UpgradeHotkeyLabel>>,
+    mut labels: Query<(&mut Text, &UpgradeHotkeyLabel)>,
+) {
+    if !bindings.is_changed() && added.is_empty() {
+        return;
+    }
+    for (mut text, label) in labels.iter_mut() {
+        let input = bindings.get(Control::Gamefield(label.0));
+        text.sections[0].value = format!("[{}]", describe_input(&input));
+    }
+}
+
 #[derive(Component, Default)]
 pub struct ColonyUpgradeButton<T: ColonyUpgrade + Default + Component> {
     marker: PhantomData<T>,
@@ -80,6 +115,7 @@ where
     fn category_icon() -> String;
     fn effect_icon() -> String;
     fn cost_icon() -> String;
+    fn action() -> GamefieldActions;
 
     fn init(mut q: Query<&mut UpgradeStringIndex, With<Colony>>) {
         let mut index = q.get_single_mut().unwrap();
@@ -94,7 +130,20 @@ where
     fn effect_tag() -> ColonyUpgradeEffect<Self> {
         ColonyUpgradeEffect::<Self>::default()
     }
-    fn spawn_button(commands: &mut Commands, asset_server: &Res<AssetServer>) -> Entity {
+    // Buys once on press, then keeps buying while the hotkey or card stays held.
+    fn buy_requested(
+        actions: &Query<&ActionState<GamefieldActions>>,
+        repeat: &mut HoldToRepeat,
+        delta: Duration,
+    ) -> bool {
+        let held = actions.iter().any(|action| action.pressed(Self::action()));
+        repeat.tick(held, delta)
+    }
+    fn spawn_button(
+        commands: &mut Commands,
+        asset_server: &Res<AssetServer>,
+        action_target: Entity,
+    ) -> Entity {
         let upgrade_button = commands
             .spawn((
                 NineSliceUiMaterialBundle {
@@ -116,9 +165,29 @@ where
                     ..default()
                 },
                 Interaction::None,
+                Focusable(UIFocus::Gamefield),
+                ActionStateDriver {
+                    action: Self::action(),
+                    targets: action_target.into(),
+                },
                 Self::button_tag(),
             ))
             .id();
+        let hotkey_label = commands
+            .spawn((
+                TextBundle {
+                    text: Text::from_section("", TextStyle::local(SMALL, Color::BLACK)),
+                    style: Style {
+                        position_type: PositionType::Absolute,
+                        top: px(3.),
+                        right: px(5.),
+                        ..default()
+                    },
+                    ..default()
+                },
+                UpgradeHotkeyLabel(Self::action()),
+            ))
+            .id();
 
         let upgrade_widget_layout_root = commands
             .spawn(NodeBundle {
@@ -273,7 +342,7 @@ where
 
         commands
             .entity(upgrade_button)
-            .push_children(&[upgrade_widget_layout_root]);
+            .push_children(&[upgrade_widget_layout_root, hotkey_label]);
         commands
             .entity(upgrade_widget_layout_root)
             .push_children(&[upgrade_widget_row_1, upgrade_widget_row_2]);
@@ -396,13 +465,12 @@ pub trait AntUpgrade: ColonyUpgrade {
     fn upgrade_ants(
         mut q: Query<(&mut UpgradeStringIndex, &mut FoodQuant), With<Colony>>,
         mut ant_settings: ResMut<AntSettings>,
-        button_q: Query<&Interaction, With<ColonyUpgradeButton<Self>>>,
+        actions: Query<&ActionState<GamefieldActions>>,
+        time: Res<Time>,
+        mut repeat: Local<HoldToRepeat>,
     ) {
         //TODO - figure this out when we have player colony id logic.
-        if button_q
-            .get_single()
-            .is_ok_and(|f| matches!(f, Interaction::Pressed))
-        {
+        if Self::buy_requested(&actions, &mut repeat, time.delta()) {
             let (mut upgrades, mut food) = q.single_mut();
             let feature_index = *upgrades.costs.get(&Self::name()).unwrap();
             let cost = Self::cost(&feature_index);
@@ -440,6 +508,9 @@ impl ColonyUpgrade for AntCarryCapacity {
     fn name() -> String {
         "Ant Carry Capacity".into()
     }
+    fn action() -> GamefieldActions {
+        GamefieldActions::BuyCarryCapacity
+    }
     fn category_icon() -> String {
         "ant_icon.png".into()
     }
@@ -522,13 +593,12 @@ impl ColonyMaxFood {
 
     fn upgrade_colony(
         mut q: Query<(&mut UpgradeStringIndex, &mut FoodQuant, &mut MaxFood), With<Colony>>,
-        button_q: Query<&Interaction, With<ColonyUpgradeButton<Self>>>,
+        actions: Query<&ActionState<GamefieldActions>>,
+        time: Res<Time>,
+        mut repeat: Local<HoldToRepeat>,
     ) {
         //TODO - figure this out when we have player colony id logic.
-        if button_q
-            .get_single()
-            .is_ok_and(|f| matches!(f, Interaction::Pressed))
-        {
+        if Self::buy_requested(&actions, &mut repeat, time.delta()) {
             let (mut upgrades, mut food, mut ant_cap) = q.single_mut();
             let cost = Self::cost(&1);
             if cost <= food.0 {
@@ -543,6 +613,9 @@ impl ColonyUpgrade for ColonyMaxFood {
     fn name() -> String {
         "Colony Max Food".into()
     }
+    fn action() -> GamefieldActions {
+        GamefieldActions::BuyMaxFood
+    }
     fn category_icon() -> String {
         "food_icon.png".into()
     }
@@ -618,13 +691,12 @@ impl AntMaxPop {
 
     fn upgrade_colony(
         mut q: Query<(&mut UpgradeStringIndex, &mut FoodQuant, &mut AntCapacity), With<Colony>>,
-        button_q: Query<&Interaction, With<ColonyUpgradeButton<Self>>>,
+        actions: Query<&ActionState<GamefieldActions>>,
+        time: Res<Time>,
+        mut repeat: Local<HoldToRepeat>,
     ) {
         //TODO - figure this out when we have player colony id logic.
-        if button_q
-            .get_single()
-            .is_ok_and(|f| matches!(f, Interaction::Pressed))
-        {
+        if Self::buy_requested(&actions, &mut repeat, time.delta()) {
             let (mut upgrades, mut food, mut ant_cap) = q.single_mut();
             let feature_index = upgrades.costs.get(&Self::name()).unwrap();
             let cost = Self::cost(feature_index);
@@ -640,6 +712,9 @@ impl ColonyUpgrade for AntMaxPop {
     fn name() -> String {
         "Ant Capacity".into()
     }
+    fn action() -> GamefieldActions {
+        GamefieldActions::BuyAntCapacity
+    }
     fn category_icon() -> String {
         "ant_icon.png".into()
     }
@@ -654,6 +729,80 @@ impl ColonyUpgrade for AntMaxPop {
     }
 }
 
+#[derive(Component, Default)]
+pub struct QueenFertility;
+impl QueenFertility {
+    // Extra eggs per sim-minute for each level.
+    fn val() -> f32 {
+        1.0
+    }
+    fn progress_bar_display_effect(
+        q: Query<&UpgradeStringIndex, With<Colony>>,
+        queen_settings: Res<QueenSettings>,
+        mut text_q: Query<&mut Text, With<ColonyUpgradeEffect<Self>>>,
+    ) {
+        let upgrades = q.single();
+        if let Some(feature_index) = upgrades.costs.get(&Self::name()) {
+            for mut text in text_q.iter_mut() {
+                text.sections[0].value = format!("{:?}", queen_settings.eggs_per_minute);
+                if *feature_index < Self::max_index() {
+                    text.sections[2].value = format!("(+{:?})", Self::val());
+                } else {
+                    text.sections[0].style = TextStyle::local(SMALL, Color::BLACK);
+                    text.sections[1].style = TextStyle::local(SMALL, Color::BLACK);
+                    text.sections[2].style = TextStyle::local(SMALL, Color::BLACK);
+                    text.sections[2].value = "MAX".into();
+                }
+            }
+        }
+    }
+
+    fn upgrade_colony(
+        mut q: Query<(&mut UpgradeStringIndex, &mut FoodQuant), With<Colony>>,
+        mut queen_settings: ResMut<QueenSettings>,
+        actions: Query<&ActionState<GamefieldActions>>,
+        time: Res<Time>,
+        mut repeat: Local<HoldToRepeat>,
+    ) {
+        //TODO - figure this out when we have player colony id logic.
+        if Self::buy_requested(&actions, &mut repeat, time.delta()) {
+            let (mut upgrades, mut food) = q.single_mut();
+            let feature_index = *upgrades.costs.get(&Self::name()).unwrap();
+            let cost = Self::cost(&feature_index);
+            if feature_index < Self::max_index() && cost <= food.0 {
+                food.0 -= cost;
+                queen_settings.eggs_per_minute += Self::val();
+                upgrades.increment_index(Self::name());
+            }
+        }
+    }
+}
+impl ColonyUpgrade for QueenFertility {
+    fn name() -> String {
+        "Queen Fertility".into()
+    }
+    fn action() -> GamefieldActions {
+        GamefieldActions::BuyQueenFertility
+    }
+    fn category_icon() -> String {
+        "egg_icon.png".into()
+    }
+    fn effect_icon() -> String {
+        "speed_icon.png".into()
+    }
+    fn cost_icon() -> String {
+        "food_icon.png".into()
+    }
+    fn cost(cost_index: &i32) -> i32 {
+        squarish(*cost_index, 4.0, 100.0) as i32
+    }
+}
+impl Maxable for QueenFertility {
+    fn max_index() -> i32 {
+        5
+    }
+}
+
 fn squarish(i: i32, flattener: f32, scalar: f32) -> f32 {
     let f = i as f32;
     (f * (f / flattener)) * scalar
@@ -0,0 +1,253 @@
+use std::{f32::consts::TAU, time::Duration};
+
+use bevy::prelude::*;
+use bevy_prng::WyRand;
+use bevy_rand::resource::GlobalEntropy;
+use rand::Rng;
+
+use crate::{
+    calendar::{Season, SimCalendar},
+    gametimer::{GameClock, SimTimer},
+    MainCamera, SimState, SoundScape,
+};
+
+pub struct WeatherPlugin;
+
+impl Plugin for WeatherPlugin {
+    fn build(&self, app: &mut App) {
+        app.insert_resource(Weather::default())
+            .add_systems(
+                OnEnter(SimState::Playing),
+                spawn_weather_timer.run_if(run_once()),
+            )
+            .add_systems(
+                Update,
+                (
+                    change_weather,
+                    tint_weather,
+                    spawn_weather_particles,
+                    move_weather_particles,
+                )
+                    .chain()
+                    .run_if(in_state(SimState::Playing)),
+            );
+    }
+}
+
+// All durations in sim-seconds.
+const CALM_MIN_SECS: u64 = 180;
+const CALM_MAX_SECS: u64 = 420;
+const EVENT_MIN_SECS: u64 = 60;
+const EVENT_MAX_SECS: u64 = 180;
+const RAIN_SCENT_DECAY_FACTOR: f32 = 4.0;
+const RAIN_SPEED_FACTOR: f32 = 0.7;
+const HEAT_UPKEEP_FACTOR: f32 = 1.75;
+const WIND_STRENGTH: f32 = 3.0;
+const WEATHER_OVERLAY_ALPHA: f32 = 0.2;
+const WEATHER_OVERLAY_SIZE: f32 = 10000.0;
+const MAX_WEATHER_PARTICLES: usize = 300;
+const WEATHER_PARTICLE_LIFETIME: f32 = 1.5;
+
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum WeatherKind {
+    Clear,
+    // Washes scent off the ground and slows everyone down.
+    Rain,
+    // Blows ants off course in the given direction.
+    Wind(Vec2),
+    // Everybody gets hungrier.
+    Heat,
+}
+
+#[derive(Resource)]
+pub struct Weather {
+    pub current: WeatherKind,
+}
+impl Default for Weather {
+    fn default() -> Self {
+        Weather {
+            current: WeatherKind::Clear,
+        }
+    }
+}
+impl Weather {
+    pub fn scent_decay_factor(&self) -> f32 {
+        match self.current {
+            WeatherKind::Rain => RAIN_SCENT_DECAY_FACTOR,
+            _ => 1.0,
+        }
+    }
+    pub fn ant_speed_factor(&self) -> f32 {
+        match self.current {
+            WeatherKind::Rain => RAIN_SPEED_FACTOR,
+            _ => 1.0,
+        }
+    }
+    pub fn upkeep_factor(&self) -> f32 {
+        match self.current {
+            WeatherKind::Heat => HEAT_UPKEEP_FACTOR,
+            _ => 1.0,
+        }
+    }
+    pub fn wind(&self) -> Vec2 {
+        match self.current {
+            WeatherKind::Wind(wind) => wind,
+            _ => Vec2::ZERO,
+        }
+    }
+    fn overlay_color(&self) -> Color {
+        match self.current {
+            WeatherKind::Clear => Color::rgba(0.0, 0.0, 0.0, 0.0),
+            WeatherKind::Rain => Color::rgba_u8(60, 70, 95, 255).with_a(WEATHER_OVERLAY_ALPHA),
+            WeatherKind::Wind(_) => {
+                Color::rgba_u8(190, 175, 140, 255).with_a(WEATHER_OVERLAY_ALPHA * 0.5)
+            }
+            WeatherKind::Heat => Color::rgba_u8(255, 140, 40, 255).with_a(WEATHER_OVERLAY_ALPHA),
+        }
+    }
+}
+
+#[derive(Component)]
+struct WeatherTimer;
+#[derive(Component)]
+struct WeatherOverlay;
+#[derive(Component)]
+struct WeatherParticle {
+    velocity: Vec2,
+    age: f32,
+}
+
+fn spawn_weather_timer(mut commands: Commands, mut rng: ResMut<GlobalEntropy<WyRand>>) {
+    commands.spawn((
+        SimTimer::once_from(Duration::from_secs(
+            rng.gen_range(CALM_MIN_SECS..CALM_MAX_SECS),
+        )),
+        WeatherTimer,
+        Name::new("Weather Timer"),
+    ));
+    commands.spawn((
+        SpriteBundle {
+            sprite: Sprite {
+                color: Color::rgba(0.0, 0.0, 0.0, 0.0),
+                custom_size: Some(Vec2::splat(WEATHER_OVERLAY_SIZE)),
+                ..default()
+            },
+            transform: Transform::from_xyz(0.0, 0.0, 49.0),
+            ..default()
+        },
+        WeatherOverlay,
+        Name::new("Weather Overlay"),
+    ));
+}
+
+// Weather alternates between a calm spell and a single event, each lasting a random amount of time.
+fn change_weather(
+    mut weather: ResMut<Weather>,
+    mut sounds: EventWriter<SoundScape>,
+    mut rng: ResMut<GlobalEntropy<WyRand>>,
+    calendar: Res<SimCalendar>,
+    mut q: Query<&mut SimTimer, With<WeatherTimer>>,
+) {
+    let Ok(mut timer) = q.get_single_mut() else {
+        return;
+    };
+    if !timer.time.finished() {
+        return;
+    }
+    let next = match weather.current {
+        WeatherKind::Clear => match rng.gen_range(0..3) {
+            // Only summer gets properly hot.
+            0 if calendar.season() == Season::Summer => WeatherKind::Heat,
+            1 => WeatherKind::Wind(Vec2::from_angle(rng.gen_range(0.0..TAU)) * WIND_STRENGTH),
+            _ => WeatherKind::Rain,
+        },
+        _ => WeatherKind::Clear,
+    };
+    let secs = match next {
+        WeatherKind::Clear => rng.gen_range(CALM_MIN_SECS..CALM_MAX_SECS),
+        _ => rng.gen_range(EVENT_MIN_SECS..EVENT_MAX_SECS),
+    };
+    info!("weather changing to {:?} for {:?}s", next, secs);
+    *timer = SimTimer::once_from(Duration::from_secs(secs));
+    weather.current = next;
+    sounds.send(SoundScape::WeatherChange);
+}
+
+fn tint_weather(weather: Res<Weather>, mut q: Query<&mut Sprite, With<WeatherOverlay>>) {
+    if !weather.is_changed() {
+        return;
+    }
+    for mut sprite in q.iter_mut() {
+        sprite.color = weather.overlay_color();
+    }
+}
+
+// Rain streaks and blowing dust, sprinkled over whatever the camera is looking at.
+fn spawn_weather_particles(
+    mut commands: Commands,
+    weather: Res<Weather>,
+    clock: Res<GameClock>,
+    mut rng: ResMut<GlobalEntropy<WyRand>>,
+    camera_q: Query<(&Transform, &OrthographicProjection), With<MainCamera>>,
+    particles: Query<(), With<WeatherParticle>>,
+) {
+    let (velocity, color, size) = match weather.current {
+        WeatherKind::Rain => (
+            Vec2::new(-20.0, -220.0),
+            Color::rgba_u8(150, 170, 230, 160),
+            Vec2::new(8.0, 1.0),
+        ),
+        WeatherKind::Wind(wind) => (
+            wind * 40.0,
+            Color::rgba_u8(200, 185, 150, 140),
+            Vec2::new(3.0, 1.0),
+        ),
+        _ => return,
+    };
+    if clock.delta.is_zero() {
+        return;
+    }
+    let Ok((camera_transform, projection)) = camera_q.get_single() else {
+        return;
+    };
+    let available = MAX_WEATHER_PARTICLES.saturating_sub(particles.iter().len());
+    let area = projection.area;
+    let center = camera_transform.translation.truncate();
+    for _ in 0..available.min(10) {
+        let pos = center
+            + Vec2::new(
+                rng.gen_range(area.min.x..area.max.x),
+                rng.gen_range(area.min.y..area.max.y),
+            );
+        let mut transform = Transform::from_xyz(pos.x, pos.y, 48.0);
+        transform.rotation = Quat::from_rotation_arc_2d(Vec2::X, velocity.normalize_or_zero());
+        commands.spawn((
+            SpriteBundle {
+                sprite: Sprite {
+                    color,
+                    custom_size: Some(size),
+                    ..default()
+                },
+                transform,
+                ..default()
+            },
+            WeatherParticle { velocity, age: 0.0 },
+        ));
+    }
+}
+
+fn move_weather_particles(
+    mut commands: Commands,
+    time: Res<Time>,
+    weather: Res<Weather>,
+    mut q: Query<(Entity, &mut Transform, &mut WeatherParticle)>,
+) {
+    for (entity, mut transform, mut particle) in q.iter_mut() {
+        particle.age += time.delta_seconds();
+        if particle.age >= WEATHER_PARTICLE_LIFETIME || weather.current == WeatherKind::Clear {
+            commands.entity(entity).despawn();
+            continue;
+        }
+        transform.translation += (particle.velocity * time.delta_seconds()).extend(0.0);
+    }
+}
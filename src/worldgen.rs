@@ -0,0 +1,244 @@
+use std::f32::consts::TAU;
+
+use bevy::prelude::*;
+use bevy_prng::WyRand;
+use bevy_rand::resource::GlobalEntropy;
+use rand::{Rng, SeedableRng};
+
+use crate::{
+    food::{spawn_food_bush, spawn_food_chunk, FoodQuant},
+    terrain::{spawn_obstacle, Obstacle, ObstacleKind},
+    SimState,
+};
+
+pub struct WorldGenPlugin;
+
+impl Plugin for WorldGenPlugin {
+    fn build(&self, app: &mut App) {
+        app.insert_resource(WorldGenSettings::default())
+            .insert_resource(WorldBounds::default())
+            .add_systems(Startup, roll_world_seed)
+            .add_systems(
+                OnEnter(SimState::Playing),
+                generate_world.run_if(run_once()),
+            );
+    }
+}
+
+const NEST_EXCLUSION: f32 = 110.0;
+const WORLD_MAX_DIST: f32 = 650.0;
+const BOUNDED_WORLD_HALF_SIZE: f32 = 700.0;
+const OPEN_WORLD_HALF_SIZE: f32 = 1000.0;
+pub const WORLD_EDGE_MARGIN: f32 = 40.0;
+const FOOD_PATCH_SPREAD: f32 = 45.0;
+const OBSTACLE_GAP: f32 = 15.0;
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum TerrainDensity {
+    Sparse,
+    Normal,
+    Dense,
+}
+impl TerrainDensity {
+    fn obstacle_count(&self) -> usize {
+        match self {
+            TerrainDensity::Sparse => 20,
+            TerrainDensity::Normal => 40,
+            TerrainDensity::Dense => 70,
+        }
+    }
+    pub fn next(&self) -> Self {
+        match self {
+            TerrainDensity::Sparse => TerrainDensity::Normal,
+            TerrainDensity::Normal => TerrainDensity::Dense,
+            TerrainDensity::Dense => TerrainDensity::Sparse,
+        }
+    }
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum FoodAbundance {
+    Scarce,
+    Normal,
+    Plentiful,
+}
+impl FoodAbundance {
+    fn patch_count(&self) -> usize {
+        match self {
+            FoodAbundance::Scarce => 3,
+            FoodAbundance::Normal => 6,
+            FoodAbundance::Plentiful => 10,
+        }
+    }
+    pub fn next(&self) -> Self {
+        match self {
+            FoodAbundance::Scarce => FoodAbundance::Normal,
+            FoodAbundance::Normal => FoodAbundance::Plentiful,
+            FoodAbundance::Plentiful => FoodAbundance::Scarce,
+        }
+    }
+}
+
+// Chosen from the main menu before the first game starts.
+#[derive(Resource)]
+pub struct WorldGenSettings {
+    pub seed: u32,
+    pub terrain: TerrainDensity,
+    pub food: FoodAbundance,
+    pub bounded: bool,
+}
+impl Default for WorldGenSettings {
+    fn default() -> Self {
+        WorldGenSettings {
+            seed: 0,
+            terrain: TerrainDensity::Normal,
+            food: FoodAbundance::Normal,
+            bounded: false,
+        }
+    }
+}
+
+// Everything happens inside this rectangle - food spawns in it, ants get nudged back into it and the camera can't leave it.
+#[derive(Resource)]
+pub struct WorldBounds(pub Rect);
+impl Default for WorldBounds {
+    fn default() -> Self {
+        WorldBounds(Rect::from_center_half_size(
+            Vec2::ZERO,
+            Vec2::splat(OPEN_WORLD_HALF_SIZE),
+        ))
+    }
+}
+impl WorldBounds {
+    pub fn contains(&self, pos: Vec2) -> bool {
+        self.0.contains(pos)
+    }
+    // The part of the world far enough from the edge that nobody needs steering back.
+    pub fn interior(&self) -> Rect {
+        self.0.inset(-WORLD_EDGE_MARGIN)
+    }
+}
+
+fn roll_world_seed(mut settings: ResMut<WorldGenSettings>, mut rng: ResMut<GlobalEntropy<WyRand>>) {
+    settings.seed = rng.gen();
+}
+
+fn generate_world(
+    mut commands: Commands,
+    assets: Res<AssetServer>,
+    mut meshes: ResMut<Assets<Mesh>>,
+    mut materials: ResMut<Assets<ColorMaterial>>,
+    settings: Res<WorldGenSettings>,
+    mut bounds: ResMut<WorldBounds>,
+) {
+    info!("generating world from seed {:?}", settings.seed);
+    let mut rng = WyRand::seed_from_u64(settings.seed as u64);
+
+    if settings.bounded {
+        let rect = Rect::from_center_half_size(Vec2::ZERO, Vec2::splat(BOUNDED_WORLD_HALF_SIZE));
+        spawn_world_border(&mut commands, rect);
+        bounds.0 = rect;
+    }
+
+    let mut placed: Vec<(Vec2, f32)> = Vec::new();
+    let mut iters = 0;
+    let obstacle_count = settings.terrain.obstacle_count();
+    while placed.len() < obstacle_count && iters < obstacle_count * 20 {
+        iters += 1;
+        let kind = ObstacleKind::random(&mut rng);
+        let (min_radius, max_radius) = kind.radius_range();
+        let radius = rng.gen_range(min_radius..max_radius);
+        let pos = random_pos_in_ring(&mut rng, NEST_EXCLUSION + radius, WORLD_MAX_DIST);
+        // Leave a gap between obstacles wide enough for ants to squeeze through.
+        if placed.iter().any(|(other, other_radius)| {
+            pos.distance(*other) < radius + other_radius + OBSTACLE_GAP
+        }) {
+            continue;
+        }
+        placed.push((pos, radius));
+        spawn_obstacle(
+            &mut commands,
+            &mut meshes,
+            &mut materials,
+            Obstacle { kind, radius },
+            pos,
+        );
+    }
+
+    // Whole patches go where ants can reach every bush and chunk in them, see FoodPlacement.
+    let patch_area = bounds.interior().inset(-FOOD_PATCH_SPREAD);
+    let mut patches = 0;
+    let mut iters = 0;
+    let patch_count = settings.food.patch_count();
+    while patches < patch_count && iters < patch_count * 20 {
+        iters += 1;
+        let patch_center = random_pos_in_ring(&mut rng, NEST_EXCLUSION * 1.5, WORLD_MAX_DIST);
+        if !patch_area.contains(patch_center)
+            || placed
+                .iter()
+                .any(|(other, radius)| patch_center.distance(*other) < radius + FOOD_PATCH_SPREAD)
+        {
+            continue;
+        }
+        patches += 1;
+        for _ in 0..rng.gen_range(2..=4) {
+            let bush_pos = patch_center
+                + Vec2::from_angle(rng.gen_range(0.0..TAU))
+                    * rng.gen_range(0.0..FOOD_PATCH_SPREAD * 0.5);
+            spawn_food_bush(&mut commands, &mut meshes, &mut materials, bush_pos, 3);
+        }
+        for _ in 0..rng.gen_range(1..=2) {
+            let chunk_pos = patch_center
+                + Vec2::from_angle(rng.gen_range(0.0..TAU)) * rng.gen_range(0.0..FOOD_PATCH_SPREAD);
+            spawn_food_chunk(
+                &mut commands,
+                &assets,
+                chunk_pos,
+                FoodQuant(rng.gen_range(10..40) * 20),
+            );
+        }
+    }
+}
+
+fn random_pos_in_ring(rng: &mut impl Rng, min: f32, max: f32) -> Vec2 {
+    Vec2::from_angle(rng.gen_range(0.0..TAU)) * rng.gen_range(min..max)
+}
+
+fn spawn_world_border(commands: &mut Commands, rect: Rect) {
+    let thickness = 4.0;
+    let color = Color::rgb_u8(89, 86, 82);
+    let size = rect.size();
+    let center = rect.center();
+    let edges = [
+        (
+            Vec2::new(center.x, rect.max.y),
+            Vec2::new(size.x, thickness),
+        ),
+        (
+            Vec2::new(center.x, rect.min.y),
+            Vec2::new(size.x, thickness),
+        ),
+        (
+            Vec2::new(rect.min.x, center.y),
+            Vec2::new(thickness, size.y),
+        ),
+        (
+            Vec2::new(rect.max.x, center.y),
+            Vec2::new(thickness, size.y),
+        ),
+    ];
+    for (pos, edge_size) in edges {
+        commands.spawn((
+            SpriteBundle {
+                sprite: Sprite {
+                    color,
+                    custom_size: Some(edge_size),
+                    ..default()
+                },
+                transform: Transform::from_xyz(pos.x, pos.y, 0.05),
+                ..default()
+            },
+            Name::new("World Border"),
+        ));
+    }
+}